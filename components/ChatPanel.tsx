@@ -1,20 +1,33 @@
 import { useEffect, useRef } from "react";
 import { useAiAction } from "../hooks/useAiAction";
 import { useChat } from "../hooks/useChat";
-import type { AiAction } from "../lib/types";
+import { saveCurrentSession } from "../hooks/useSession";
+import { AI_ACTIONS } from "../lib/ai-actions";
+import { timeRangeError, useMeetingStore } from "../stores/meetingStore";
 import { ChatInput } from "./ui/ChatInput";
 import { ChatMessage } from "./ui/ChatMessage";
 
-const AI_ACTIONS: { id: AiAction; label: string }[] = [
-	{ id: "recap", label: "Recap" },
-	{ id: "assist", label: "Assist" },
-	{ id: "question", label: "Question" },
-	{ id: "action", label: "Action" },
+const RESPONSE_LANGUAGES: { code: string; label: string }[] = [
+	{ code: "", label: "Auto" },
+	{ code: "en", label: "English" },
+	{ code: "ja", label: "日本語" },
+	{ code: "zh", label: "中文" },
+	{ code: "ko", label: "한국어" },
+	{ code: "es", label: "Español" },
+	{ code: "fr", label: "Français" },
+	{ code: "de", label: "Deutsch" },
 ];
 
 export function ChatPanel() {
 	const { chatHistory, sendMessage, isLoading: isChatLoading } = useChat();
 	const { executeAction, isLoading: isAiLoading } = useAiAction();
+	const responseLanguage = useMeetingStore((s) => s.responseLanguage);
+	const setResponseLanguage = useMeetingStore((s) => s.setResponseLanguage);
+	const contextNote = useMeetingStore((s) => s.contextNote);
+	const setContextNote = useMeetingStore((s) => s.setContextNote);
+	const timeRange = useMeetingStore((s) => s.timeRange);
+	const setTimeRange = useMeetingStore((s) => s.setTimeRange);
+	const rangeError = timeRangeError(timeRange);
 	const bottomRef = useRef<HTMLDivElement>(null);
 
 	const isLoading = isChatLoading || isAiLoading;
@@ -26,6 +39,45 @@ export function ChatPanel() {
 
 	return (
 		<div className="flex flex-col flex-1">
+			<div className="border-b px-3 py-2">
+				<input
+					type="text"
+					value={contextNote}
+					onChange={(e) => setContextNote(e.target.value)}
+					placeholder="Meeting context for AI (e.g. Rust architecture review)"
+					className="w-full rounded-lg border border-gray-300 px-3 py-1.5 text-xs focus:outline-none focus:ring-2 focus:ring-blue-500"
+				/>
+				<div className="mt-1.5 flex items-center gap-1.5 text-xs text-gray-500">
+					<span>Only from</span>
+					<input
+						type="text"
+						value={timeRange.from}
+						onChange={(e) => setTimeRange({ from: e.target.value })}
+						placeholder="10:05"
+						className="w-16 rounded border border-gray-300 px-1.5 py-0.5 focus:outline-none focus:ring-1 focus:ring-blue-500"
+					/>
+					<span>to</span>
+					<input
+						type="text"
+						value={timeRange.to}
+						onChange={(e) => setTimeRange({ to: e.target.value })}
+						placeholder="10:20"
+						className="w-16 rounded border border-gray-300 px-1.5 py-0.5 focus:outline-none focus:ring-1 focus:ring-blue-500"
+					/>
+					{(timeRange.from || timeRange.to) && (
+						<button
+							type="button"
+							onClick={() => setTimeRange({ from: "", to: "" })}
+							className="text-gray-400 hover:text-gray-600"
+						>
+							Clear
+						</button>
+					)}
+				</div>
+				{rangeError && (
+					<p className="mt-1 text-xs text-red-500">{rangeError}</p>
+				)}
+			</div>
 			<div className="flex-1 overflow-y-auto p-4 space-y-3">
 				{chatHistory.length === 0 ? (
 					<div className="flex items-center justify-center h-full text-gray-400 text-sm">
@@ -34,7 +86,15 @@ export function ChatPanel() {
 				) : (
 					chatHistory.map((msg, i) => (
 						// biome-ignore lint/suspicious/noArrayIndexKey: chat messages have no stable ID
-						<ChatMessage key={i} message={msg} />
+						<ChatMessage
+							key={i}
+							message={msg}
+							onFeedback={(feedback) => {
+								useMeetingStore.getState().setChatFeedback(i, feedback);
+								// Auto-save only watches message count
+								saveCurrentSession();
+							}}
+						/>
 					))
 				)}
 				{isLoading && (
@@ -49,7 +109,7 @@ export function ChatPanel() {
 				)}
 				<div ref={bottomRef} />
 			</div>
-			<div className="flex gap-2 px-3 pb-2">
+			<div className="flex flex-wrap gap-2 px-3 pb-2">
 				{AI_ACTIONS.map((action) => (
 					<button
 						key={action.id}
@@ -61,6 +121,18 @@ export function ChatPanel() {
 						{action.label}
 					</button>
 				))}
+				<select
+					value={responseLanguage ?? ""}
+					onChange={(e) => setResponseLanguage(e.target.value || null)}
+					className="rounded-lg border border-gray-300 px-1.5 text-xs"
+					title="Response language"
+				>
+					{RESPONSE_LANGUAGES.map((l) => (
+						<option key={l.code} value={l.code}>
+							{l.label}
+						</option>
+					))}
+				</select>
 			</div>
 			<ChatInput onSend={sendMessage} disabled={isLoading} />
 		</div>
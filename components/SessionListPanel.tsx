@@ -1,5 +1,7 @@
+import { useRef, useState } from "react";
 import { useSessionList } from "../hooks/useSessionList";
 import { messenger } from "../lib/messaging";
+import { parseSessionExport } from "../lib/session-export";
 import type { SessionSummary } from "../lib/types";
 
 function SessionItem({
@@ -12,15 +14,14 @@ function SessionItem({
 	onDelete: () => void;
 }) {
 	const date = new Date(session.createdAt);
-	const dateStr = date.toLocaleDateString("en-US", {
+	const dateStr = date.toLocaleDateString(undefined, {
 		month: "short",
 		day: "numeric",
 		year: "numeric",
 	});
-	const timeStr = date.toLocaleTimeString("en-US", {
+	const timeStr = date.toLocaleTimeString(undefined, {
 		hour: "2-digit",
 		minute: "2-digit",
-		hour12: false,
 	});
 
 	return (
@@ -70,8 +71,52 @@ function SessionItem({
 	);
 }
 
+function ImportButton({ existingIds }: { existingIds: string[] }) {
+	const inputRef = useRef<HTMLInputElement>(null);
+	const [error, setError] = useState<string | null>(null);
+
+	const handleFile = async (file: File) => {
+		setError(null);
+		try {
+			const session = parseSessionExport(await file.text());
+			// Don't overwrite a stored session when the same file is imported twice
+			if (existingIds.includes(session.id)) {
+				session.id = crypto.randomUUID();
+			}
+			await messenger.sendMessage("session:save", session);
+		} catch (e) {
+			setError(e instanceof Error ? e.message : "Import failed.");
+		}
+	};
+
+	return (
+		<div className="flex items-center justify-end gap-2 text-xs">
+			{error && <span className="text-red-500 truncate">{error}</span>}
+			<input
+				ref={inputRef}
+				type="file"
+				accept="application/json,.json"
+				className="hidden"
+				onChange={(e) => {
+					const file = e.target.files?.[0];
+					e.target.value = "";
+					if (file) handleFile(file);
+				}}
+			/>
+			<button
+				type="button"
+				onClick={() => inputRef.current?.click()}
+				className="px-2 py-1 text-gray-500 hover:text-gray-700 hover:bg-gray-100 rounded transition-colors"
+			>
+				Import JSON
+			</button>
+		</div>
+	);
+}
+
 export function SessionListPanel() {
 	const { sessions, isLoading, deleteSession } = useSessionList();
+	const existingIds = sessions.map((s) => s.id);
 
 	const handleSelect = (id: string) => {
 		messenger.sendMessage("session:open-viewer", { id });
@@ -87,14 +132,16 @@ export function SessionListPanel() {
 
 	if (sessions.length === 0) {
 		return (
-			<div className="flex items-center justify-center h-full text-gray-400 text-sm">
+			<div className="flex flex-col items-center justify-center gap-2 h-full text-gray-400 text-sm">
 				No saved sessions yet.
+				<ImportButton existingIds={existingIds} />
 			</div>
 		);
 	}
 
 	return (
 		<div className="p-4 space-y-2">
+			<ImportButton existingIds={existingIds} />
 			{sessions.map((session) => (
 				<SessionItem
 					key={session.id}
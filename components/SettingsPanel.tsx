@@ -1,6 +1,10 @@
-import type { FormEvent } from "react";
+import { type FormEvent, useEffect, useState } from "react";
 import { useSettings } from "../hooks/useSettings";
+import { actionLabel } from "../lib/ai-actions";
 import type { ProviderConfigMap, ProviderId } from "../lib/ai-provider";
+import { CONTEXT_LIMITS, type ContextSettings } from "../lib/context-settings";
+import { messenger } from "../lib/messaging";
+import type { AiAction, AiFeedbackStats, AppInfo } from "../lib/types";
 
 const PROVIDERS: { id: ProviderId; label: string }[] = [
 	{ id: "anthropic", label: "Anthropic" },
@@ -131,11 +135,147 @@ function Field({
 	);
 }
 
+function ContextForm({
+	settings,
+	onChange,
+}: {
+	settings: ContextSettings;
+	onChange: (partial: Partial<ContextSettings>) => void;
+}) {
+	const limits =
+		settings.mode === "count"
+			? CONTEXT_LIMITS.recentUtterances
+			: CONTEXT_LIMITS.recentMinutes;
+	return (
+		<fieldset className="space-y-2">
+			<legend className="text-sm font-medium text-gray-700">
+				AI context
+			</legend>
+			<div className="flex items-center gap-2">
+				<input
+					type="number"
+					min={limits.min}
+					max={limits.max}
+					value={
+						settings.mode === "count"
+							? settings.recentUtterances
+							: settings.recentMinutes
+					}
+					onChange={(e) =>
+						onChange(
+							settings.mode === "count"
+								? { recentUtterances: Number(e.target.value) }
+								: { recentMinutes: Number(e.target.value) },
+						)
+					}
+					className="w-24 rounded-lg border border-gray-300 px-3 py-2 text-sm focus:outline-none focus:ring-2 focus:ring-blue-500"
+				/>
+				<select
+					value={settings.mode}
+					onChange={(e) =>
+						onChange({ mode: e.target.value as ContextSettings["mode"] })
+					}
+					className="rounded-lg border border-gray-300 px-2 py-2 text-sm"
+				>
+					<option value="count">most recent utterances</option>
+					<option value="minutes">most recent minutes</option>
+				</select>
+			</div>
+			<p className="text-xs text-gray-500">
+				Between {limits.min} and {limits.max}.
+			</p>
+			<div className="flex items-center gap-2 text-sm">
+				<span className="text-gray-700">Keep at most</span>
+				<input
+					type="number"
+					min={CONTEXT_LIMITS.maxChatMessages.min}
+					max={CONTEXT_LIMITS.maxChatMessages.max}
+					value={settings.maxChatMessages}
+					disabled={settings.preserveAllChat}
+					onChange={(e) =>
+						onChange({ maxChatMessages: Number(e.target.value) })
+					}
+					className="w-20 rounded-lg border border-gray-300 px-2 py-1 text-sm disabled:opacity-50 focus:outline-none focus:ring-2 focus:ring-blue-500"
+				/>
+				<span className="text-gray-700">chat messages per session</span>
+			</div>
+			<label className="flex items-center gap-2 text-xs text-gray-500">
+				<input
+					type="checkbox"
+					checked={settings.preserveAllChat}
+					onChange={(e) => onChange({ preserveAllChat: e.target.checked })}
+					className="accent-blue-500"
+				/>
+				Keep all chat messages
+			</label>
+			<p className="text-xs text-gray-500">
+				Recaps and action items are never removed.
+			</p>
+		</fieldset>
+	);
+}
+
+const FEEDBACK_DAYS = 30;
+
+function FeedbackSection() {
+	const [stats, setStats] = useState<AiFeedbackStats | null>(null);
+
+	useEffect(() => {
+		const since = new Date(Date.now() - FEEDBACK_DAYS * 24 * 60 * 60 * 1000);
+		messenger
+			.sendMessage("ai:feedback-stats", { since: since.toISOString() })
+			.then(setStats);
+	}, []);
+
+	if (!stats || Object.keys(stats).length === 0) return null;
+
+	return (
+		<section className="border-t pt-4 text-xs text-gray-500 space-y-0.5">
+			<h3 className="text-sm font-medium text-gray-700">
+				AI feedback (last {FEEDBACK_DAYS} days)
+			</h3>
+			{Object.entries(stats).map(([action, counts]) => (
+				<p key={action}>
+					{action === "chat" ? "Chat" : actionLabel(action as AiAction)}:{" "}
+					{counts.used} used · {counts.helpful} helpful ·{" "}
+					{counts["not-helpful"]} not helpful
+				</p>
+			))}
+		</section>
+	);
+}
+
+function AboutSection() {
+	const [info, setInfo] = useState<AppInfo | null>(null);
+
+	useEffect(() => {
+		messenger.sendMessage("app:info", undefined).then(setInfo);
+	}, []);
+
+	if (!info) return null;
+
+	return (
+		<section className="border-t pt-4 text-xs text-gray-500 space-y-0.5">
+			<h3 className="text-sm font-medium text-gray-700">About</h3>
+			<p>Kanpe v{info.version}</p>
+			<p>
+				Chrome {info.browserVersion} · {info.os}/{info.arch}
+			</p>
+			<p>
+				{info.sessionCount} sessions · {info.utteranceCount} utterances stored
+			</p>
+		</section>
+	);
+}
+
 export function SettingsPanel() {
 	const {
 		settings,
+		statuses,
 		updateProvider,
 		updateProviderConfig,
+		contextSettings,
+		updateContextSettings,
 		save,
 		isSaving,
 		saveResult,
@@ -167,6 +307,9 @@ export function SettingsPanel() {
 								className="accent-blue-500"
 							/>
 							<span className="text-sm">{label}</span>
+							{statuses.some((st) => st.id === id && !st.configured) && (
+								<span className="text-xs text-gray-400">Not configured</span>
+							)}
 						</label>
 					))}
 				</fieldset>
@@ -193,6 +336,13 @@ export function SettingsPanel() {
 					)}
 				</div>
 
+				<div className="border-t pt-4">
+					<ContextForm
+						settings={contextSettings}
+						onChange={updateContextSettings}
+					/>
+				</div>
+
 				{saveResult === "success" && (
 					<p className="text-sm text-green-500">Settings saved successfully.</p>
 				)}
@@ -208,6 +358,9 @@ export function SettingsPanel() {
 					{isSaving ? "Saving..." : "Save"}
 				</button>
 			</form>
+
+			<FeedbackSection />
+			<AboutSection />
 		</div>
 	);
 }
@@ -1,28 +1,139 @@
-import { useEffect, useRef } from "react";
+import { type ReactNode, useEffect, useRef, useState } from "react";
+import { saveCurrentSession } from "../hooks/useSession";
+import type { Marker, Utterance } from "../lib/types";
 import { useMeetingStore } from "../stores/meetingStore";
+import { MarkerItem } from "./ui/MarkerItem";
 import { UtteranceItem } from "./ui/UtteranceItem";
 
+export function TranscriptEntries({
+	utterances,
+	markers,
+	renderActions,
+}: {
+	utterances: Utterance[];
+	markers: Marker[];
+	/** Optional per-utterance controls, shown on hover. */
+	renderActions?: (index: number) => ReactNode;
+}) {
+	// Markers have no id; their position in the append-only list is stable,
+	// while time + label repeats for unlabeled markers in the same second
+	const keyed = markers.map((marker, k) => ({ marker, key: `marker-${k}` }));
+	// Markers dropped before the first caption render at the top
+	const leading = keyed.filter(({ marker }) => marker.utteranceIndex < 0);
+
+	return (
+		<>
+			{leading.map(({ marker, key }) => (
+				<MarkerItem key={key} marker={marker} />
+			))}
+			{utterances.map((u, i) => (
+				<div key={`${u.time}-${u.speaker}`} className="space-y-2">
+					{renderActions ? (
+						<div className="group relative">
+							<UtteranceItem utterance={u} />
+							<div className="absolute top-0 right-0 hidden group-hover:flex gap-1 bg-white">
+								{renderActions(i)}
+							</div>
+						</div>
+					) : (
+						<UtteranceItem utterance={u} />
+					)}
+					{keyed
+						.filter(({ marker }) => marker.utteranceIndex === i)
+						.map(({ marker, key }) => (
+							<MarkerItem key={key} marker={marker} />
+						))}
+				</div>
+			))}
+		</>
+	);
+}
+
+function TranscriptToolbar() {
+	const sessionId = useMeetingStore((s) => s.sessionId);
+	const addMarker = useMeetingStore((s) => s.addMarker);
+	const [label, setLabel] = useState("");
+	const utteranceCount = useMeetingStore((s) => s.utterances.length);
+	const [savedCount, setSavedCount] = useState<number | null>(null);
+
+	// New captions make the last checkpoint stale
+	// biome-ignore lint/correctness/useExhaustiveDependencies: reset on new utterances
+	useEffect(() => {
+		setSavedCount(null);
+	}, [utteranceCount]);
+
+	const handleMark = () => {
+		addMarker(label);
+		setLabel("");
+	};
+
+	// Checkpoint: save now instead of waiting for the debounced auto-save
+	const handleSave = async () => {
+		setSavedCount(await saveCurrentSession());
+	};
+
+	return (
+		<div className="border-t p-3 flex gap-2">
+			<input
+				type="text"
+				value={label}
+				onChange={(e) => setLabel(e.target.value)}
+				onKeyDown={(e) => {
+					if (e.key === "Enter" && !e.nativeEvent.isComposing) handleMark();
+				}}
+				placeholder="Marker label (optional)"
+				disabled={!sessionId}
+				className="flex-1 rounded-lg border border-gray-300 px-3 py-2 text-sm focus:outline-none focus:ring-2 focus:ring-blue-500 disabled:opacity-50"
+			/>
+			<button
+				type="button"
+				onClick={handleMark}
+				disabled={!sessionId}
+				className="px-4 py-2 bg-amber-500 text-white text-sm rounded-lg hover:bg-amber-600 disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
+				title="Mark this moment (Alt+Shift+M)"
+			>
+				Mark
+			</button>
+			<button
+				type="button"
+				onClick={handleSave}
+				disabled={!sessionId}
+				className="px-3 py-2 bg-gray-100 text-sm rounded-lg hover:bg-gray-200 disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
+				title={
+					savedCount === null
+						? "Save a checkpoint now"
+						: `Saved ${savedCount} utterances`
+				}
+			>
+				{savedCount === null ? "Save" : "Saved"}
+			</button>
+		</div>
+	);
+}
+
 export function TranscriptPanel() {
 	const utterances = useMeetingStore((s) => s.utterances);
+	const markers = useMeetingStore((s) => s.markers);
 	const bottomRef = useRef<HTMLDivElement>(null);
 
-	// biome-ignore lint/correctness/useExhaustiveDependencies: scroll on new utterances
+	// biome-ignore lint/correctness/useExhaustiveDependencies: scroll on new utterances and markers
 	useEffect(() => {
 		bottomRef.current?.scrollIntoView({ behavior: "smooth" });
-	}, [utterances.length]);
+	}, [utterances.length, markers.length]);
 
 	return (
-		<div className="flex-1 overflow-y-auto p-4 space-y-2">
-			{utterances.length === 0 ? (
-				<div className="flex items-center justify-center h-full text-gray-400 text-sm">
-					Waiting for captions...
-				</div>
-			) : (
-				utterances.map((u) => (
-					<UtteranceItem key={`${u.time}-${u.speaker}`} utterance={u} />
-				))
-			)}
-			<div ref={bottomRef} />
+		<div className="flex flex-col h-full">
+			<div className="flex-1 overflow-y-auto p-4 space-y-2">
+				{utterances.length === 0 ? (
+					<div className="flex items-center justify-center h-full text-gray-400 text-sm">
+						Waiting for captions...
+					</div>
+				) : (
+					<TranscriptEntries utterances={utterances} markers={markers} />
+				)}
+				<div ref={bottomRef} />
+			</div>
+			<TranscriptToolbar />
 		</div>
 	);
 }
@@ -1,8 +1,57 @@
 import ReactMarkdown from "react-markdown";
 import remarkGfm from "remark-gfm";
-import type { ChatMessage as ChatMessageType } from "../../lib/types";
+import {
+	hasFeedback,
+	normalizeFeedback,
+	toggleFeedback,
+} from "../../lib/ai-feedback";
+import type {
+	AiFeedback,
+	AiFeedbackOption,
+	ChatMessage as ChatMessageType,
+} from "../../lib/types";
 
-export function ChatMessage({ message }: { message: ChatMessageType }) {
+const FEEDBACK_OPTIONS: { id: AiFeedbackOption; label: string }[] = [
+	{ id: "used", label: "Used" },
+	{ id: "helpful", label: "\u{1F44D}" },
+	{ id: "not-helpful", label: "\u{1F44E}" },
+];
+
+function FeedbackButtons({
+	value,
+	onChange,
+}: {
+	value?: AiFeedback;
+	onChange: (feedback: AiFeedback | undefined) => void;
+}) {
+	return (
+		<div className="flex gap-1 mt-1">
+			{FEEDBACK_OPTIONS.map((f) => (
+				<button
+					key={f.id}
+					type="button"
+					aria-pressed={hasFeedback(value, f.id)}
+					onClick={() => onChange(toggleFeedback(value, f.id))}
+					className={`px-1.5 py-0.5 text-xs rounded transition-colors ${
+						hasFeedback(value, f.id)
+							? "bg-blue-100 text-blue-700"
+							: "text-gray-400 hover:bg-gray-100 hover:text-gray-600"
+					}`}
+				>
+					{f.label}
+				</button>
+			))}
+		</div>
+	);
+}
+
+export function ChatMessage({
+	message,
+	onFeedback,
+}: {
+	message: ChatMessageType;
+	onFeedback?: (feedback: AiFeedback | undefined) => void;
+}) {
 	const isUser = message.role === "user";
 	return (
 		<div className={`flex ${isUser ? "justify-end" : "justify-start"}`}>
@@ -20,6 +69,17 @@ export function ChatMessage({ message }: { message: ChatMessageType }) {
 						</ReactMarkdown>
 					</div>
 				)}
+				{message.range && (
+					<div className="mt-1 text-xs text-gray-400">
+						Covers {message.range.from}–{message.range.to}
+					</div>
+				)}
+				{!isUser && message.action && onFeedback && (
+					<FeedbackButtons
+						value={normalizeFeedback(message.feedback)}
+						onChange={onFeedback}
+					/>
+				)}
 			</div>
 		</div>
 	);
@@ -0,0 +1,14 @@
+import type { Marker } from "../../lib/types";
+
+export function MarkerItem({ marker }: { marker: Marker }) {
+	return (
+		<div className="flex gap-2 text-sm">
+			<span className="text-amber-500 shrink-0 font-mono text-xs mt-0.5">
+				{marker.time}
+			</span>
+			<div className="flex-1 border-l-2 border-amber-400 bg-amber-50 px-2 text-amber-800">
+				{"\u{1F6A9}"} {marker.label || "Marker"}
+			</div>
+		</div>
+	);
+}
@@ -1,25 +1,56 @@
-import { messenger } from "../lib/messaging";
+import { AI_ACTIONS, resolveAction } from "../lib/ai-actions";
+import { tallyFeedback } from "../lib/ai-feedback";
+import {
+	getContextSettings,
+	setContextSettings,
+} from "../lib/context-settings";
+import { SIDEPANEL_PORT, messenger } from "../lib/messaging";
 import {
 	CHAT_SYSTEM,
 	PROMPTS,
 	TITLE_GENERATION,
 	buildChatMessages,
+	buildTranscriptContext,
+	formatMarkers,
 	formatTranscript,
+	isValidLanguageCode,
+	selectTimeRange,
 	truncateTranscript,
+	withResponseLanguage,
+	withTimeRange,
 } from "../lib/prompts";
 import {
 	getProviderSettings,
 	setProviderSettings,
 } from "../lib/provider-settings";
-import { ApiError, classifyApiError, getProvider } from "../lib/providers";
+import {
+	ApiError,
+	classifyApiError,
+	getProvider,
+	listProviderStatuses,
+} from "../lib/providers";
 import {
 	deleteSession,
 	getSession,
 	getSessionIndex,
+	pruneChatHistory,
+	pruneStoredChatHistories,
+	remapSessionSpeakers,
 	saveSession,
+	splitSession,
+	toSessionSummary,
+	trimSession,
+	updateSessionContext,
 	updateSessionTitle,
 } from "../lib/session-storage";
-import type { AiAction } from "../lib/types";
+import type {
+	AiResponse,
+	ChatMessage,
+	Marker,
+	SessionsChange,
+	TimeRange,
+	Utterance,
+} from "../lib/types";
 
 export default defineBackground(() => {
 	console.log("[kanpe] Background service worker started");
@@ -27,11 +58,60 @@ export default defineBackground(() => {
 	// Track active Meet tabs for end detection
 	const activeMeetTabs = new Set<number>();
 
+	// Session the side panel is still recording (its auto-save would clobber
+	// edits). Kept in session storage so it survives service worker restarts.
+	const LIVE_SESSION_KEY = "liveSessionId";
+
+	async function getLiveSessionId(): Promise<string | null> {
+		const { [LIVE_SESSION_KEY]: id } =
+			await chrome.storage.session.get(LIVE_SESSION_KEY);
+		return (id as string | undefined) ?? null;
+	}
+
+	async function setLiveSessionId(id: string | null): Promise<void> {
+		await chrome.storage.session.set({ [LIVE_SESSION_KEY]: id });
+	}
+
+	// The side panel holds a port open; when it closes, unlock its session
+	chrome.runtime.onConnect.addListener((port) => {
+		if (port.name !== SIDEPANEL_PORT) return;
+		port.onDisconnect.addListener(() => {
+			setLiveSessionId(null);
+		});
+	});
+
 	// Relay captions from Content Script to Side Panel
 	messenger.onMessage("caption:new", ({ data }) => {
 		messenger.sendMessage("caption:relay", data);
 	});
 
+	// Helper: a response carrying an error message instead of an AI reply
+	function errorResponse(action: string, content: string): AiResponse {
+		return {
+			action,
+			content,
+			timestamp: new Date().toISOString(),
+			error: true,
+		};
+	}
+
+	// Helper: pick the utterances and markers sent to the AI, or an error for
+	// a bad range. Without a range the context settings apply.
+	async function selectContext(data: {
+		utterances: Utterance[];
+		markers?: Marker[];
+		range?: TimeRange;
+	}) {
+		if (data.range) {
+			return selectTimeRange(data.utterances, data.markers, data.range);
+		}
+		const settings = await getContextSettings();
+		return {
+			utterances: truncateTranscript(data.utterances, settings),
+			markers: data.markers ?? [],
+		};
+	}
+
 	// Handle AI action requests
 	messenger.onMessage("ai:request", async ({ data }) => {
 		const settings = await getProviderSettings();
@@ -40,26 +120,56 @@ export default defineBackground(() => {
 
 		const validationError = provider.validateConfig(config);
 		if (validationError) {
-			return {
-				action: data.action,
-				content: validationError,
-				timestamp: new Date().toISOString(),
-			};
+			return errorResponse(data.action, validationError);
+		}
+
+		const { strict = true } = data;
+		const action = resolveAction(data.action) ?? (strict ? null : "assist");
+		if (!action) {
+			const valid = AI_ACTIONS.map((a) => a.id).join(", ");
+			return errorResponse(
+				data.action,
+				`Unknown action "${data.action}". Valid actions: ${valid}.`,
+			);
+		}
+		console.debug("[kanpe] AI action resolved:", data.action, "→", action);
+
+		const language = data.responseLanguage;
+		if (language !== undefined && !isValidLanguageCode(language)) {
+			return errorResponse(
+				action,
+				`Invalid response language "${language}". Use a code like "en" or "pt-BR".`,
+			);
+		}
+
+		const selected = await selectContext(data);
+		if (typeof selected === "string") {
+			return errorResponse(action, selected);
 		}
 
 		try {
-			const truncated = truncateTranscript(data.utterances);
-			const transcript = formatTranscript(truncated);
-			const prompt = PROMPTS[data.action as AiAction];
+			const transcript =
+				formatTranscript(selected.utterances) +
+				formatMarkers(selected.markers, data.utterances);
+			const prompt = PROMPTS[action];
 			const result = await provider.call(
-				prompt.system,
-				[{ role: "user", content: prompt.userTemplate(transcript) }],
+				{
+					system: withTimeRange(
+						withResponseLanguage(prompt.system, language),
+						data.range,
+					),
+					context: buildTranscriptContext(transcript, data.contextNote),
+					messages: [{ role: "user", content: prompt.instruction }],
+					maxTokens: prompt.maxTokens,
+				},
 				config,
 			);
 			return {
-				action: data.action,
+				action,
 				content: result,
 				timestamp: new Date().toISOString(),
+				language,
+				range: data.range,
 			};
 		} catch (e) {
 			if (e instanceof ApiError) {
@@ -71,11 +181,7 @@ export default defineBackground(() => {
 				e instanceof ApiError
 					? classifyApiError(e.status, e.body)
 					: "Network error. Please check your connection.";
-			return {
-				action: data.action,
-				content: message,
-				timestamp: new Date().toISOString(),
-			};
+			return errorResponse(action, message);
 		}
 	});
 
@@ -87,26 +193,46 @@ export default defineBackground(() => {
 
 		const validationError = provider.validateConfig(config);
 		if (validationError) {
-			return {
-				action: "chat",
-				content: validationError,
-				timestamp: new Date().toISOString(),
-			};
+			return errorResponse("chat", validationError);
+		}
+
+		const language = data.responseLanguage;
+		if (language !== undefined && !isValidLanguageCode(language)) {
+			return errorResponse(
+				"chat",
+				`Invalid response language "${language}". Use a code like "en" or "pt-BR".`,
+			);
+		}
+
+		const selected = await selectContext(data);
+		if (typeof selected === "string") {
+			return errorResponse("chat", selected);
 		}
 
 		try {
-			const truncated = truncateTranscript(data.utterances);
-			const transcript = formatTranscript(truncated);
-			const messages = buildChatMessages(
-				transcript,
-				data.history,
-				data.message,
+			const transcript =
+				formatTranscript(selected.utterances) +
+				formatMarkers(selected.markers, data.utterances);
+			const result = await provider.call(
+				{
+					system: withTimeRange(
+						withResponseLanguage(CHAT_SYSTEM, language),
+						data.range,
+					),
+					context: buildTranscriptContext(transcript, data.contextNote),
+					messages: buildChatMessages(
+						await capChatHistory(data.history),
+						data.message,
+					),
+				},
+				config,
 			);
-			const result = await provider.call(CHAT_SYSTEM, messages, config);
 			return {
 				action: "chat",
 				content: result,
 				timestamp: new Date().toISOString(),
+				language,
+				range: data.range,
 			};
 		} catch (e) {
 			if (e instanceof ApiError) {
@@ -118,11 +244,7 @@ export default defineBackground(() => {
 				e instanceof ApiError
 					? classifyApiError(e.status, e.body)
 					: "Network error. Please check your connection.";
-			return {
-				action: "chat",
-				content: message,
-				timestamp: new Date().toISOString(),
-			};
+			return errorResponse("chat", message);
 		}
 	});
 
@@ -135,28 +257,141 @@ export default defineBackground(() => {
 		await setProviderSettings(data);
 	});
 
+	messenger.onMessage("settings:getContextSettings", async () => {
+		return await getContextSettings();
+	});
+
+	messenger.onMessage("settings:setContextSettings", async ({ data }) => {
+		await setContextSettings(data);
+	});
+
+	messenger.onMessage("settings:listProviders", async () => {
+		return listProviderStatuses(await getProviderSettings());
+	});
+
+	messenger.onMessage("ai:feedback-stats", async ({ data }) => {
+		const since = data.since ? new Date(data.since).getTime() : 0;
+		const histories: ChatMessage[][] = [];
+		for (const summary of await getSessionIndex()) {
+			if (new Date(summary.createdAt).getTime() < since) continue;
+			const session = await getSession(summary.id);
+			if (session) histories.push(session.chatHistory);
+		}
+		return tallyFeedback(histories);
+	});
+
 	// Session handlers
 	messenger.onMessage("session:list", async () => {
 		return await getSessionIndex();
 	});
 
+	// Helper: apply the per-session chat cap unless the user keeps everything
+	async function capChatHistory(history: ChatMessage[]) {
+		return pruneChatHistory(history, await getContextSettings());
+	}
+
+	// Sessions saved before the chat cap existed are pruned once, on update
+	chrome.runtime.onInstalled.addListener(async ({ reason }) => {
+		if (reason !== "update") return;
+		const updated = await pruneStoredChatHistories(
+			await getContextSettings(),
+			await getLiveSessionId(),
+		);
+		if (updated.length > 0) notifySessionsChanged({ updated });
+	});
+
 	messenger.onMessage("session:get", async ({ data }) => {
 		return await getSession(data.id);
 	});
 
+	// Helper: broadcast a session list diff so every open page stays in sync
+	function notifySessionsChanged(change: Partial<SessionsChange>) {
+		messenger.sendMessage("session:changed", {
+			added: [],
+			removed: [],
+			updated: [],
+			...change,
+		});
+	}
+
 	messenger.onMessage("session:save", async ({ data }) => {
-		await saveSession(data);
+		const session = {
+			...data,
+			chatHistory: await capChatHistory(data.chatHistory),
+		};
+		// Auto-save runs every few seconds; only tell lists about real changes
+		const result = await saveSession(session);
+		if (result === "created") {
+			notifySessionsChanged({ added: [toSessionSummary(session)] });
+		} else if (result === "changed") {
+			notifySessionsChanged({ updated: [session.id] });
+		}
 	});
 
 	messenger.onMessage("session:delete", async ({ data }) => {
 		await deleteSession(data.id);
+		notifySessionsChanged({ removed: [data.id] });
 	});
 
 	messenger.onMessage("session:update-title", async ({ data }) => {
 		await updateSessionTitle(data.id, data.title);
+		notifySessionsChanged({ updated: [data.id] });
 	});
 
-	messenger.onMessage("session:generate-title", async ({ data }) => {
+	messenger.onMessage("session:set-live", async ({ data }) => {
+		await setLiveSessionId(data.id);
+	});
+
+	messenger.onMessage("session:split", async ({ data }) => {
+		if (data.id === (await getLiveSessionId())) {
+			throw new Error("Cannot split a session that is still being recorded.");
+		}
+		const session = await getSession(data.id);
+		if (!session) throw new Error("Session not found.");
+		if (data.at <= 0 || data.at >= session.utterances.length) {
+			throw new Error(
+				`Split index must be between 1 and ${session.utterances.length - 1}.`,
+			);
+		}
+
+		const newId = await splitSession(data.id, data.at);
+		if (!newId) return null;
+
+		// Retitle both halves; keep the fallback titles if the AI call fails
+		for (const id of [data.id, newId]) {
+			const part = await getSession(id);
+			if (!part) continue;
+			try {
+				await updateSessionTitle(
+					id,
+					await generateTitle(part.utterances, part.contextNote),
+				);
+			} catch (e) {
+				console.warn("[kanpe] Failed to retitle split session", id, e);
+			}
+		}
+		const index = await getSessionIndex();
+		notifySessionsChanged({
+			added: index.filter((s) => s.id === newId),
+			updated: [data.id],
+		});
+		return newId;
+	});
+
+	messenger.onMessage("session:trim", async ({ data }) => {
+		if (data.id === (await getLiveSessionId())) {
+			throw new Error("Cannot trim a session that is still being recorded.");
+		}
+		const trimmed = await trimSession(data.id, data.start, data.end);
+		if (trimmed) notifySessionsChanged({ updated: [data.id] });
+		return trimmed;
+	});
+
+	// Helper: generate a session title with the active provider
+	async function generateTitle(
+		utterances: Utterance[],
+		contextNote?: string,
+	): Promise<string> {
 		const settings = await getProviderSettings();
 		const provider = getProvider(settings.activeProvider);
 		const config = settings.configs[settings.activeProvider];
@@ -166,14 +401,42 @@ export default defineBackground(() => {
 			throw new Error(validationError);
 		}
 
-		const truncated = truncateTranscript(data.utterances);
+		const truncated = truncateTranscript(
+			utterances,
+			await getContextSettings(),
+		);
 		const transcript = formatTranscript(truncated);
 		const result = await provider.call(
-			TITLE_GENERATION.system,
-			[{ role: "user", content: TITLE_GENERATION.userTemplate(transcript) }],
+			{
+				system: TITLE_GENERATION.system,
+				context: buildTranscriptContext(transcript, contextNote),
+				messages: [{ role: "user", content: TITLE_GENERATION.instruction }],
+			},
 			config,
 		);
 		return result.trim();
+	}
+
+	messenger.onMessage("session:generate-title", async ({ data }) => {
+		return await generateTitle(data.utterances, data.contextNote);
+	});
+
+	messenger.onMessage("session:remap-speakers", async ({ data }) => {
+		if (data.id === (await getLiveSessionId())) {
+			throw new Error("Cannot rename speakers while the session is recording.");
+		}
+		await remapSessionSpeakers(data.id, data.mapping);
+		notifySessionsChanged({ updated: [data.id] });
+	});
+
+	messenger.onMessage("session:set-context", async ({ data }) => {
+		if (data.id === (await getLiveSessionId())) {
+			throw new Error(
+				"Edit the context of a recording session in the side panel.",
+			);
+		}
+		await updateSessionContext(data.id, data.note);
+		notifySessionsChanged({ updated: [data.id] });
 	});
 
 	// Relay Meet URL from Content Script to Side Panel & track tab
@@ -186,6 +449,21 @@ export default defineBackground(() => {
 		messenger.sendMessage("meet:url:relay", data);
 	});
 
+	messenger.onMessage("app:info", async () => {
+		// Counts come from the index only; no session bodies are read
+		const index = await getSessionIndex();
+		const { os, arch } = await chrome.runtime.getPlatformInfo();
+		return {
+			version: chrome.runtime.getManifest().version,
+			os,
+			arch,
+			browserVersion:
+				navigator.userAgent.match(/Chrome\/([\d.]+)/)?.[1] ?? "unknown",
+			sessionCount: index.length,
+			utteranceCount: index.reduce((sum, s) => sum + s.utteranceCount, 0),
+		};
+	});
+
 	// Open session viewer in popup window
 	messenger.onMessage("session:open-viewer", ({ data }) => {
 		chrome.windows.create({
@@ -196,6 +474,13 @@ export default defineBackground(() => {
 		});
 	});
 
+	// Keyboard shortcut: drop an unlabeled marker in the live session
+	chrome.commands.onCommand.addListener((command) => {
+		if (command === "add-marker") {
+			messenger.sendMessage("marker:add:relay", undefined);
+		}
+	});
+
 	// Helper: send meet:ended:relay to side panel
 	function notifyMeetEnded(tabId: number) {
 		if (!activeMeetTabs.has(tabId)) return;
@@ -1,16 +1,20 @@
 import { useCallback, useEffect, useRef, useState } from "react";
 import { messenger } from "../../lib/messaging";
 import type { ChatMessage, Session } from "../../lib/types";
+import { ExportMenu } from "./components/ExportMenu";
 import { SessionChatPane } from "./components/SessionChatPane";
 import { SessionTranscriptPane } from "./components/SessionTranscriptPane";
+import { SpeakerEditor } from "./components/SpeakerEditor";
 
 function EditableTitle({
 	title,
 	utterances,
+	contextNote,
 	onSave,
 }: {
 	title: string;
 	utterances: Session["utterances"];
+	contextNote?: string;
 	onSave: (newTitle: string) => void;
 }) {
 	const [isEditing, setIsEditing] = useState(false);
@@ -50,6 +54,7 @@ function EditableTitle({
 		try {
 			const generated = await messenger.sendMessage("session:generate-title", {
 				utterances,
+				contextNote,
 			});
 			setDraft(generated);
 			onSave(generated);
@@ -149,9 +154,31 @@ function EditableTitle({
 	);
 }
 
+function EditAction({
+	label,
+	title,
+	onClick,
+}: {
+	label: string;
+	title: string;
+	onClick: () => void;
+}) {
+	return (
+		<button
+			type="button"
+			onClick={onClick}
+			className="px-1.5 py-0.5 text-xs text-gray-500 bg-gray-100 hover:bg-gray-200 rounded transition-colors"
+			title={title}
+		>
+			{label}
+		</button>
+	);
+}
+
 export default function App() {
 	const [session, setSession] = useState<Session | null>(null);
 	const [error, setError] = useState<string | null>(null);
+	const [notice, setNotice] = useState<string | null>(null);
 
 	useEffect(() => {
 		const params = new URLSearchParams(window.location.search);
@@ -177,6 +204,21 @@ export default function App() {
 		messenger.sendMessage("session:save", updated);
 	};
 
+	const handleContextSave = async (input: HTMLInputElement) => {
+		const note = input.value;
+		if (!session || note.trim() === (session.contextNote ?? "")) return;
+		try {
+			await messenger.sendMessage("session:set-context", {
+				id: session.id,
+				note,
+			});
+			setSession({ ...session, contextNote: note.trim() || undefined });
+		} catch (e) {
+			input.value = session.contextNote ?? "";
+			setNotice(e instanceof Error ? e.message : "Failed to save context.");
+		}
+	};
+
 	const handleTitleSave = (newTitle: string) => {
 		if (!session) return;
 		setSession({ ...session, title: newTitle });
@@ -186,6 +228,53 @@ export default function App() {
 		});
 	};
 
+	const reloadSession = async (id: string) => {
+		const data = await messenger.sendMessage("session:get", { id });
+		if (data) setSession(data);
+	};
+
+	const handleSplit = async (at: number) => {
+		if (!session) return;
+		try {
+			const newId = await messenger.sendMessage("session:split", {
+				id: session.id,
+				at,
+			});
+			if (!newId) return;
+			await reloadSession(session.id);
+			messenger.sendMessage("session:open-viewer", { id: newId });
+		} catch (e) {
+			setNotice(e instanceof Error ? e.message : "Failed to split session.");
+		}
+	};
+
+	const handleTrim = async (start: number, end: number) => {
+		if (!session) return;
+		try {
+			const ok = await messenger.sendMessage("session:trim", {
+				id: session.id,
+				start,
+				end,
+			});
+			if (ok) await reloadSession(session.id);
+		} catch (e) {
+			setNotice(e instanceof Error ? e.message : "Failed to trim session.");
+		}
+	};
+
+	const handleRemapSpeakers = async (mapping: Record<string, string>) => {
+		if (!session) return;
+		try {
+			await messenger.sendMessage("session:remap-speakers", {
+				id: session.id,
+				mapping,
+			});
+			await reloadSession(session.id);
+		} catch (e) {
+			setNotice(e instanceof Error ? e.message : "Failed to rename speakers.");
+		}
+	};
+
 	if (error) {
 		return (
 			<div className="flex items-center justify-center h-screen text-gray-500 text-sm">
@@ -205,20 +294,74 @@ export default function App() {
 	return (
 		<div className="flex flex-col h-screen bg-white text-gray-900">
 			<header className="border-b px-4 py-3">
-				<EditableTitle
-					title={session.title}
-					utterances={session.utterances}
-					onSave={handleTitleSave}
-				/>
+				<div className="flex items-center justify-between gap-4">
+					<div className="min-w-0 flex-1">
+						<EditableTitle
+							title={session.title}
+							utterances={session.utterances}
+							contextNote={session.contextNote}
+							onSave={handleTitleSave}
+						/>
+					</div>
+					<ExportMenu session={session} />
+				</div>
 				<p className="text-xs text-gray-500 mt-0.5">
 					{new Date(session.createdAt).toLocaleString()} ·{" "}
 					{session.utterances.length} utterances
+					{(session.markers?.length ?? 0) > 0 &&
+						` · ${session.markers?.length} markers`}
 				</p>
+				<input
+					type="text"
+					defaultValue={session.contextNote ?? ""}
+					onBlur={(e) => handleContextSave(e.target)}
+					placeholder="Meeting context for AI (e.g. Rust architecture review)"
+					className="mt-1.5 w-full rounded border border-gray-200 px-2 py-1 text-xs focus:outline-none focus:ring-1 focus:ring-blue-400"
+				/>
+				<SpeakerEditor
+					key={session.utterances.map((u) => u.speaker).join("\n")}
+					utterances={session.utterances}
+					onApply={handleRemapSpeakers}
+				/>
+				{notice && <p className="text-xs text-red-500 mt-0.5">{notice}</p>}
 			</header>
 			<main className="flex flex-1 min-h-0">
-				<SessionTranscriptPane utterances={session.utterances} />
+				<SessionTranscriptPane
+					utterances={session.utterances}
+					markers={session.markers ?? []}
+					renderActions={(i) => {
+						const count = session.utterances.length;
+						return (
+							<>
+								{i > 0 && (
+									<EditAction
+										label="Start here"
+										title="Delete everything before this line"
+										onClick={() => handleTrim(i, count)}
+									/>
+								)}
+								{i < count - 1 && (
+									<EditAction
+										label="End here"
+										title="Delete everything after this line"
+										onClick={() => handleTrim(0, i + 1)}
+									/>
+								)}
+								{i > 0 && (
+									<EditAction
+										label="Split"
+										title="Move this line and everything after it into a new session"
+										onClick={() => handleSplit(i)}
+									/>
+								)}
+							</>
+						);
+					}}
+				/>
 				<SessionChatPane
 					utterances={session.utterances}
+					markers={session.markers ?? []}
+					contextNote={session.contextNote}
 					chatHistory={session.chatHistory}
 					onChatUpdate={handleChatUpdate}
 				/>
@@ -0,0 +1,54 @@
+import { useState } from "react";
+import {
+	EXPORT_FORMATS,
+	type ExportFormat,
+	downloadFile,
+	exportSession,
+} from "../../../lib/session-export";
+import type { Session } from "../../../lib/types";
+
+export function ExportMenu({ session }: { session: Session }) {
+	const [format, setFormat] = useState<ExportFormat>("csv");
+	const [bom, setBom] = useState(false);
+
+	const handleExport = () => {
+		const { filename, content, mimeType } = exportSession(session, format, {
+			bom,
+		});
+		downloadFile(filename, content, mimeType);
+	};
+
+	return (
+		<div className="flex items-center gap-2 text-xs">
+			<select
+				value={format}
+				onChange={(e) => setFormat(e.target.value as ExportFormat)}
+				className="rounded border border-gray-300 px-1.5 py-1 text-xs"
+			>
+				{EXPORT_FORMATS.map((f) => (
+					<option key={f.id} value={f.id}>
+						{f.label}
+					</option>
+				))}
+			</select>
+			{format.endsWith("csv") && (
+				<label className="flex items-center gap-1 text-gray-500">
+					<input
+						type="checkbox"
+						checked={bom}
+						onChange={(e) => setBom(e.target.checked)}
+						className="accent-blue-500"
+					/>
+					Excel (BOM)
+				</label>
+			)}
+			<button
+				type="button"
+				onClick={handleExport}
+				className="px-2 py-1 bg-gray-100 hover:bg-gray-200 rounded transition-colors"
+			>
+				Export
+			</button>
+		</div>
+	);
+}
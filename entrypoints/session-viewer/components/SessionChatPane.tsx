@@ -1,26 +1,26 @@
 import { useEffect, useRef, useState } from "react";
 import { ChatInput } from "../../../components/ui/ChatInput";
 import { ChatMessage } from "../../../components/ui/ChatMessage";
+import { AI_ACTIONS, actionLabel } from "../../../lib/ai-actions";
 import { messenger } from "../../../lib/messaging";
 import type {
 	AiAction,
+	AiFeedback,
 	ChatMessage as ChatMessageType,
+	Marker,
 	Utterance,
 } from "../../../lib/types";
 
-const AI_ACTIONS: { id: AiAction; label: string }[] = [
-	{ id: "recap", label: "Recap" },
-	{ id: "assist", label: "Assist" },
-	{ id: "question", label: "Question" },
-	{ id: "action", label: "Action" },
-];
-
 export function SessionChatPane({
 	utterances,
+	markers,
+	contextNote,
 	chatHistory: initialChatHistory,
 	onChatUpdate,
 }: {
 	utterances: Utterance[];
+	markers: Marker[];
+	contextNote?: string;
 	chatHistory: ChatMessageType[];
 	onChatUpdate: (chatHistory: ChatMessageType[]) => void;
 }) {
@@ -35,8 +35,10 @@ export function SessionChatPane({
 	}, [chatHistory.length]);
 
 	const executeAction = async (action: AiAction) => {
-		const label = AI_ACTIONS.find((a) => a.id === action)?.label ?? action;
-		const userMsg: ChatMessageType = { role: "user", content: label };
+		const userMsg: ChatMessageType = {
+			role: "user",
+			content: actionLabel(action),
+		};
 		const updatedWithUser = [...chatHistory, userMsg];
 		setChatHistory(updatedWithUser);
 		setIsLoading(true);
@@ -45,10 +47,14 @@ export function SessionChatPane({
 			const response = await messenger.sendMessage("ai:request", {
 				action,
 				utterances,
+				markers,
+				contextNote,
 			});
 			const assistantMsg: ChatMessageType = {
 				role: "assistant",
 				content: response.content,
+				// Errors get no action, so they take no feedback and can be pruned
+				action: response.error ? undefined : action,
 			};
 			const updatedWithAssistant = [...updatedWithUser, assistantMsg];
 			setChatHistory(updatedWithAssistant);
@@ -77,10 +83,13 @@ export function SessionChatPane({
 				message,
 				utterances,
 				history: updatedWithUser,
+				markers,
+				contextNote,
 			});
 			const assistantMsg: ChatMessageType = {
 				role: "assistant",
 				content: response.content,
+				action: response.error ? undefined : "chat",
 			};
 			const updatedWithAssistant = [...updatedWithUser, assistantMsg];
 			setChatHistory(updatedWithAssistant);
@@ -98,6 +107,14 @@ export function SessionChatPane({
 		}
 	};
 
+	const handleFeedback = (index: number, feedback: AiFeedback | undefined) => {
+		const updated = chatHistory.map((m, i) =>
+			i === index ? { ...m, feedback } : m,
+		);
+		setChatHistory(updated);
+		onChatUpdate(updated);
+	};
+
 	return (
 		<div className="w-1/2 flex flex-col">
 			<div className="flex-1 overflow-y-auto p-4 space-y-3">
@@ -108,7 +125,11 @@ export function SessionChatPane({
 				) : (
 					chatHistory.map((msg, i) => (
 						// biome-ignore lint/suspicious/noArrayIndexKey: chat messages have no stable ID
-						<ChatMessage key={i} message={msg} />
+						<ChatMessage
+							key={i}
+							message={msg}
+							onFeedback={(feedback) => handleFeedback(i, feedback)}
+						/>
 					))
 				)}
 				{isLoading && (
@@ -123,7 +144,7 @@ export function SessionChatPane({
 				)}
 				<div ref={bottomRef} />
 			</div>
-			<div className="flex gap-2 px-3 pb-2">
+			<div className="flex flex-wrap gap-2 px-3 pb-2">
 				{AI_ACTIONS.map((action) => (
 					<button
 						key={action.id}
@@ -1,10 +1,15 @@
-import { UtteranceItem } from "../../../components/ui/UtteranceItem";
-import type { Utterance } from "../../../lib/types";
+import type { ReactNode } from "react";
+import { TranscriptEntries } from "../../../components/TranscriptPanel";
+import type { Marker, Utterance } from "../../../lib/types";
 
 export function SessionTranscriptPane({
 	utterances,
+	markers,
+	renderActions,
 }: {
 	utterances: Utterance[];
+	markers: Marker[];
+	renderActions?: (index: number) => ReactNode;
 }) {
 	return (
 		<div className="w-1/2 border-r overflow-y-auto p-4 space-y-2">
@@ -13,9 +18,11 @@ export function SessionTranscriptPane({
 					No transcript data.
 				</div>
 			) : (
-				utterances.map((u) => (
-					<UtteranceItem key={`${u.time}-${u.speaker}`} utterance={u} />
-				))
+				<TranscriptEntries
+					utterances={utterances}
+					markers={markers}
+					renderActions={renderActions}
+				/>
 			)}
 		</div>
 	);
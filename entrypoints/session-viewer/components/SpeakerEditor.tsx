@@ -0,0 +1,49 @@
+import { useState } from "react";
+import type { Utterance } from "../../../lib/types";
+
+export function SpeakerEditor({
+	utterances,
+	onApply,
+}: {
+	utterances: Utterance[];
+	onApply: (mapping: Record<string, string>) => void;
+}) {
+	const speakers = [...new Set(utterances.map((u) => u.speaker))];
+	const [mapping, setMapping] = useState<Record<string, string>>({});
+
+	const changed = Object.entries(mapping).some(
+		([from, to]) => to.trim() && to.trim() !== from,
+	);
+
+	const handleApply = () => {
+		onApply(mapping);
+		setMapping({});
+	};
+
+	return (
+		<div className="mt-1.5 flex flex-wrap items-center gap-2 text-xs">
+			<span className="text-gray-500">Speakers:</span>
+			{speakers.map((speaker) => (
+				<input
+					key={speaker}
+					type="text"
+					value={mapping[speaker] ?? speaker}
+					onChange={(e) =>
+						setMapping((prev) => ({ ...prev, [speaker]: e.target.value }))
+					}
+					className="w-32 rounded border border-gray-200 px-1.5 py-0.5 focus:outline-none focus:ring-1 focus:ring-blue-400"
+					title={`Rename "${speaker}"`}
+				/>
+			))}
+			{changed && (
+				<button
+					type="button"
+					onClick={handleApply}
+					className="px-2 py-0.5 bg-blue-500 text-white rounded hover:bg-blue-600 transition-colors"
+				>
+					Rename
+				</button>
+			)}
+		</div>
+	);
+}
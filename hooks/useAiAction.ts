@@ -1,30 +1,33 @@
 import { useState } from "react";
+import { actionLabel } from "../lib/ai-actions";
 import { messenger } from "../lib/messaging";
 import type { AiAction } from "../lib/types";
-import { useMeetingStore } from "../stores/meetingStore";
-
-const ACTION_LABELS: Record<AiAction, string> = {
-	recap: "Recap",
-	assist: "Assist",
-	question: "Question",
-	action: "Action",
-};
+import { activeTimeRange, useMeetingStore } from "../stores/meetingStore";
 
 export function useAiAction() {
 	const [isLoading, setIsLoading] = useState(false);
 
 	const executeAction = async (action: AiAction) => {
 		const store = useMeetingStore.getState();
-		store.addChatMessage({ role: "user", content: ACTION_LABELS[action] });
+		store.addChatMessage({ role: "user", content: actionLabel(action) });
 		setIsLoading(true);
 		try {
 			const response = await messenger.sendMessage("ai:request", {
 				action,
 				utterances: store.utterances,
+				markers: store.markers,
+				responseLanguage: store.responseLanguage ?? undefined,
+				contextNote: store.contextNote,
+				range: activeTimeRange(store.timeRange),
+			});
+			useMeetingStore.getState().addChatMessage({
+				role: "assistant",
+				content: response.content,
+				language: response.language,
+				range: response.range,
+				// Errors get no action, so they take no feedback and can be pruned
+				action: response.error ? undefined : action,
 			});
-			useMeetingStore
-				.getState()
-				.addChatMessage({ role: "assistant", content: response.content });
 		} catch {
 			useMeetingStore.getState().addChatMessage({
 				role: "assistant",
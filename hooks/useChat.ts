@@ -1,6 +1,6 @@
 import { useState } from "react";
 import { messenger } from "../lib/messaging";
-import { useMeetingStore } from "../stores/meetingStore";
+import { activeTimeRange, useMeetingStore } from "../stores/meetingStore";
 
 export function useChat() {
 	const chatHistory = useMeetingStore((s) => s.chatHistory);
@@ -17,10 +17,18 @@ export function useChat() {
 				message,
 				utterances,
 				history: store.chatHistory,
+				markers: store.markers,
+				responseLanguage: store.responseLanguage ?? undefined,
+				contextNote: store.contextNote,
+				range: activeTimeRange(store.timeRange),
+			});
+			useMeetingStore.getState().addChatMessage({
+				role: "assistant",
+				content: response.content,
+				language: response.language,
+				range: response.range,
+				action: response.error ? undefined : "chat",
 			});
-			useMeetingStore
-				.getState()
-				.addChatMessage({ role: "assistant", content: response.content });
 		} catch {
 			useMeetingStore.getState().addChatMessage({
 				role: "assistant",
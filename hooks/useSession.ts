@@ -1,18 +1,18 @@
 import { useEffect, useRef } from "react";
-import { messenger } from "../lib/messaging";
+import { SIDEPANEL_PORT, messenger } from "../lib/messaging";
 import type { Session } from "../lib/types";
 import { useMeetingStore } from "../stores/meetingStore";
 
+// Date and time follow the browser's locale (e.g. 24-hour clock for ja-JP)
 function buildSessionTitle(meetUrl: string | null, createdAt: string): string {
 	const date = new Date(createdAt);
-	const dateStr = date.toLocaleDateString("en-US", {
+	const dateStr = date.toLocaleDateString(undefined, {
 		month: "2-digit",
 		day: "2-digit",
 	});
-	const timeStr = date.toLocaleTimeString("en-US", {
+	const timeStr = date.toLocaleTimeString(undefined, {
 		hour: "2-digit",
 		minute: "2-digit",
-		hour12: false,
 	});
 
 	if (meetUrl) {
@@ -33,19 +33,29 @@ function buildCurrentSession(): Session | null {
 		createdAt: state.sessionCreatedAt,
 		utterances: state.utterances,
 		chatHistory: state.chatHistory,
+		markers: state.markers,
+		responseLanguage: state.responseLanguage ?? undefined,
+		contextNote: state.contextNote.trim() || undefined,
 	};
 }
 
-async function saveCurrentSession(): Promise<void> {
+/**
+ * Persist the current session immediately.
+ * Returns the number of utterances saved (0 when there is nothing to save).
+ */
+export async function saveCurrentSession(): Promise<number> {
 	const session = buildCurrentSession();
 	if (session && session.utterances.length > 0) {
 		await messenger.sendMessage("session:save", session);
+		return session.utterances.length;
 	}
+	return 0;
 }
 
 export function useSession(): void {
 	const utterances = useMeetingStore((s) => s.utterances);
 	const chatHistory = useMeetingStore((s) => s.chatHistory);
+	const markers = useMeetingStore((s) => s.markers);
 	const sessionId = useMeetingStore((s) => s.sessionId);
 
 	const saveTimerRef = useRef<ReturnType<typeof setTimeout> | null>(null);
@@ -62,6 +72,30 @@ export function useSession(): void {
 		}
 	}, [utterances.length, sessionId]);
 
+	// Tell background which session is live so it refuses to edit it
+	useEffect(() => {
+		messenger.sendMessage("session:set-live", { id: sessionId });
+	}, [sessionId]);
+
+	// Keep a port open so background unlocks the session when the panel
+	// closes. The port also drops when the service worker is stopped; the
+	// lock survives that in session storage, so just reconnect.
+	useEffect(() => {
+		let port: chrome.runtime.Port | null = null;
+		let closed = false;
+		const connect = () => {
+			port = chrome.runtime.connect({ name: SIDEPANEL_PORT });
+			port.onDisconnect.addListener(() => {
+				if (!closed) connect();
+			});
+		};
+		connect();
+		return () => {
+			closed = true;
+			port?.disconnect();
+		};
+	}, []);
+
 	// Debounced auto-save (5s)
 	// biome-ignore lint/correctness/useExhaustiveDependencies: trigger save on data changes
 	useEffect(() => {
@@ -75,7 +109,7 @@ export function useSession(): void {
 		return () => {
 			if (saveTimerRef.current) clearTimeout(saveTimerRef.current);
 		};
-	}, [utterances.length, chatHistory.length, sessionId]);
+	}, [utterances.length, chatHistory.length, markers.length, sessionId]);
 
 	// beforeunload — final save
 	useEffect(() => {
@@ -94,6 +128,14 @@ export function useSession(): void {
 		return removeListener;
 	}, []);
 
+	// Listen for marker:add:relay (keyboard shortcut)
+	useEffect(() => {
+		const removeListener = messenger.onMessage("marker:add:relay", () => {
+			useMeetingStore.getState().addMarker();
+		});
+		return removeListener;
+	}, []);
+
 	// Listen for meet:ended:relay — save and reset
 	useEffect(() => {
 		const removeListener = messenger.onMessage("meet:ended:relay", async () => {
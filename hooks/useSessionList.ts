@@ -28,5 +28,18 @@ export function useSessionList() {
 		refresh();
 	}, [refresh]);
 
+	// Keep in sync with changes made from other pages (viewer, live session)
+	useEffect(() => {
+		return messenger.onMessage("session:changed", ({ data }) => {
+			setSessions((prev) => [
+				...data.added.filter((a) => !prev.some((s) => s.id === a.id)),
+				...prev.filter((s) => !data.removed.includes(s.id)),
+			]);
+			if (data.updated.length > 0) {
+				messenger.sendMessage("session:list", undefined).then(setSessions);
+			}
+		});
+	}, []);
+
 	return { sessions, isLoading, deleteSession, refresh };
 }
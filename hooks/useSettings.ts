@@ -1,5 +1,13 @@
-import { useEffect, useState } from "react";
-import type { ProviderId, ProviderSettings } from "../lib/ai-provider";
+import { useCallback, useEffect, useState } from "react";
+import type {
+	ProviderId,
+	ProviderSettings,
+	ProviderStatus,
+} from "../lib/ai-provider";
+import {
+	type ContextSettings,
+	DEFAULT_CONTEXT_SETTINGS,
+} from "../lib/context-settings";
 import { messenger } from "../lib/messaging";
 import { DEFAULT_SETTINGS } from "../lib/provider-settings";
 
@@ -9,12 +17,28 @@ export function useSettings() {
 	const [saveResult, setSaveResult] = useState<"success" | "error" | null>(
 		null,
 	);
+	const [statuses, setStatuses] = useState<ProviderStatus[]>([]);
+	const [contextSettings, setContextSettings] = useState<ContextSettings>(
+		DEFAULT_CONTEXT_SETTINGS,
+	);
+
+	const refreshStatuses = useCallback(
+		() =>
+			messenger
+				.sendMessage("settings:listProviders", undefined)
+				.then(setStatuses),
+		[],
+	);
 
 	useEffect(() => {
 		messenger
 			.sendMessage("settings:getProviderSettings", undefined)
 			.then(setSettings);
-	}, []);
+		refreshStatuses();
+		messenger
+			.sendMessage("settings:getContextSettings", undefined)
+			.then(setContextSettings);
+	}, [refreshStatuses]);
 
 	const updateProvider = (id: ProviderId) => {
 		setSaveResult(null);
@@ -35,12 +59,26 @@ export function useSettings() {
 		}));
 	};
 
+	const updateContextSettings = (partial: Partial<ContextSettings>) => {
+		setSaveResult(null);
+		setContextSettings((prev) => ({ ...prev, ...partial }));
+	};
+
 	const save = async () => {
 		setIsSaving(true);
 		setSaveResult(null);
 		try {
 			await messenger.sendMessage("settings:setProviderSettings", settings);
+			await messenger.sendMessage(
+				"settings:setContextSettings",
+				contextSettings,
+			);
+			// Show the values as stored (out-of-range numbers are clamped)
+			setContextSettings(
+				await messenger.sendMessage("settings:getContextSettings", undefined),
+			);
 			setSaveResult("success");
+			refreshStatuses();
 		} catch {
 			setSaveResult("error");
 		} finally {
@@ -50,8 +88,11 @@ export function useSettings() {
 
 	return {
 		settings,
+		statuses,
 		updateProvider,
 		updateProviderConfig,
+		contextSettings,
+		updateContextSettings,
 		save,
 		isSaving,
 		saveResult,
@@ -0,0 +1,35 @@
+import type { Session } from "../types";
+
+/**
+ * A session using every stored field. Keys are deliberately out of the
+ * export order so tests can tell the output order is fixed.
+ */
+export const FIXTURE_SESSION: Session = {
+	chatHistory: [
+		{
+			content: "[2 earlier messages were pruned]",
+			role: "assistant",
+			pruned: 2,
+		},
+		{ role: "user", content: "What did we decide?" },
+		{
+			role: "assistant",
+			content: "Ship on Friday.",
+			action: "recap",
+			language: "en",
+			feedback: { rating: "helpful", used: true },
+			range: { to: "10:05", from: "10:00" },
+		},
+	],
+	utterances: [
+		{ text: "Let's ship on Friday.", speaker: "Aoi", time: "10:01:15" },
+		{ speaker: "Ren", text: "Agreed, \"Friday\" it is.", time: "10:02:40" },
+	],
+	markers: [{ utteranceIndex: 1, label: "Decision", time: "10:02:41" }],
+	title: "Release sync",
+	id: "session-1",
+	responseLanguage: "en",
+	createdAt: "2026-10-01T01:00:00.000Z",
+	contextNote: "Weekly release meeting",
+	meetUrl: "https://meet.google.com/abc-defg-hij",
+};
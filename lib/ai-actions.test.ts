@@ -0,0 +1,32 @@
+import { describe, expect, it } from "vitest";
+import { AI_ACTIONS, actionLabel, resolveAction } from "./ai-actions";
+
+describe("resolveAction", () => {
+	it("matches every built-in id", () => {
+		for (const { id } of AI_ACTIONS) {
+			expect(resolveAction(id)).toBe(id);
+		}
+	});
+
+	it("ignores case", () => {
+		expect(resolveAction("RECAP")).toBe("recap");
+		expect(resolveAction("Objection")).toBe("objection");
+	});
+
+	it("ignores surrounding whitespace", () => {
+		expect(resolveAction("  Assist \n")).toBe("assist");
+		expect(resolveAction("\texplain")).toBe("explain");
+	});
+
+	it("rejects unknown or empty input", () => {
+		expect(resolveAction("summarize")).toBeNull();
+		expect(resolveAction("re cap")).toBeNull();
+		expect(resolveAction("   ")).toBeNull();
+	});
+});
+
+describe("actionLabel", () => {
+	it("returns the button label", () => {
+		expect(actionLabel("question")).toBe("Question");
+	});
+});
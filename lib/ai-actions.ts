@@ -0,0 +1,21 @@
+import type { AiAction } from "./types";
+
+/** Built-in AI actions, in button order. Prompts live in PROMPTS. */
+export const AI_ACTIONS: { id: AiAction; label: string; icon: string }[] = [
+	{ id: "recap", label: "Recap", icon: "\u{1F4CB}" },
+	{ id: "assist", label: "Assist", icon: "\u{1F4A1}" },
+	{ id: "question", label: "Question", icon: "\u{2753}" },
+	{ id: "action", label: "Action", icon: "\u{2705}" },
+	{ id: "objection", label: "Objection", icon: "\u{1F6E1}" },
+	{ id: "explain", label: "Explain", icon: "\u{1F4AC}" },
+];
+
+/** Match an action id case-insensitively, ignoring surrounding whitespace. */
+export function resolveAction(input: string): AiAction | null {
+	const key = input.trim().toLowerCase();
+	return AI_ACTIONS.find((a) => a.id === key)?.id ?? null;
+}
+
+export function actionLabel(action: AiAction): string {
+	return AI_ACTIONS.find((a) => a.id === action)?.label ?? action;
+}
@@ -0,0 +1,91 @@
+import { describe, expect, it } from "vitest";
+import {
+	hasFeedback,
+	normalizeFeedback,
+	tallyFeedback,
+	toggleFeedback,
+} from "./ai-feedback";
+import type { ChatMessage } from "./types";
+
+describe("toggleFeedback", () => {
+	it("records used and a rating together", () => {
+		const used = toggleFeedback(undefined, "used");
+		expect(toggleFeedback(used, "helpful")).toEqual({
+			used: true,
+			rating: "helpful",
+		});
+	});
+
+	it("keeps the rating when used is toggled off", () => {
+		expect(toggleFeedback({ used: true, rating: "helpful" }, "used")).toEqual(
+			{ rating: "helpful" },
+		);
+	});
+
+	it("lets one rating replace the other", () => {
+		expect(toggleFeedback({ rating: "helpful" }, "not-helpful")).toEqual({
+			rating: "not-helpful",
+		});
+	});
+
+	it("clears feedback once nothing is selected", () => {
+		expect(toggleFeedback({ rating: "helpful" }, "helpful")).toBeUndefined();
+		expect(toggleFeedback({ used: true }, "used")).toBeUndefined();
+	});
+});
+
+describe("hasFeedback", () => {
+	it("checks used and the rating separately", () => {
+		const feedback = { used: true, rating: "not-helpful" } as const;
+		expect(hasFeedback(feedback, "used")).toBe(true);
+		expect(hasFeedback(feedback, "not-helpful")).toBe(true);
+		expect(hasFeedback(feedback, "helpful")).toBe(false);
+		expect(hasFeedback(undefined, "used")).toBe(false);
+	});
+});
+
+describe("normalizeFeedback", () => {
+	it("reads the single-option strings of older sessions", () => {
+		expect(normalizeFeedback("used")).toEqual({ used: true });
+		expect(normalizeFeedback("helpful")).toEqual({ rating: "helpful" });
+	});
+
+	it("drops unknown values", () => {
+		expect(normalizeFeedback("great")).toBeUndefined();
+		expect(normalizeFeedback({ used: "yes", rating: "meh" })).toBeUndefined();
+		expect(normalizeFeedback({ used: true, extra: 1 })).toEqual({
+			used: true,
+		});
+	});
+});
+
+describe("tallyFeedback", () => {
+	const reply = (
+		action: ChatMessage["action"],
+		feedback?: unknown,
+	): ChatMessage => ({
+		role: "assistant",
+		content: "…",
+		action,
+		feedback: feedback as ChatMessage["feedback"],
+	});
+
+	it("counts used and ratings per action across sessions", () => {
+		const stats = tallyFeedback([
+			[
+				reply("assist", { used: true, rating: "helpful" }),
+				reply("assist", { rating: "not-helpful" }),
+				reply("recap"),
+			],
+			[reply("assist", "used"), reply("chat", { used: true })],
+		]);
+		expect(stats).toEqual({
+			assist: { used: 2, helpful: 1, "not-helpful": 1 },
+			chat: { used: 1, helpful: 0, "not-helpful": 0 },
+		});
+	});
+
+	it("skips replies without an action", () => {
+		expect(tallyFeedback([[reply(undefined, { used: true })]])).toEqual({});
+	});
+});
@@ -0,0 +1,64 @@
+import type {
+	AiFeedback,
+	AiFeedbackOption,
+	AiFeedbackStats,
+	ChatMessage,
+} from "./types";
+
+/** Whether `option` is currently selected in `feedback`. */
+export function hasFeedback(
+	feedback: AiFeedback | undefined,
+	option: AiFeedbackOption,
+): boolean {
+	return option === "used" ? !!feedback?.used : feedback?.rating === option;
+}
+
+/**
+ * Flip one feedback button. "used" toggles on its own; the ratings replace
+ * each other. Returns undefined once nothing is selected.
+ */
+export function toggleFeedback(
+	feedback: AiFeedback | undefined,
+	option: AiFeedbackOption,
+): AiFeedback | undefined {
+	const on = !hasFeedback(feedback, option);
+	const used = option === "used" ? on : !!feedback?.used;
+	const rating = option === "used" ? feedback?.rating : on ? option : undefined;
+	if (!used && !rating) return undefined;
+	const result: AiFeedback = {};
+	if (used) result.used = true;
+	if (rating) result.rating = rating;
+	return result;
+}
+
+/**
+ * Read stored feedback. Sessions saved before `used` and the rating were split
+ * hold a single option string.
+ */
+export function normalizeFeedback(value: unknown): AiFeedback | undefined {
+	if (value === "used" || value === "helpful" || value === "not-helpful") {
+		return toggleFeedback(undefined, value);
+	}
+	if (typeof value !== "object" || value === null) return undefined;
+	const { used, rating } = value as Record<string, unknown>;
+	const result: AiFeedback = {};
+	if (used === true) result.used = true;
+	if (rating === "helpful" || rating === "not-helpful") result.rating = rating;
+	return result.used || result.rating ? result : undefined;
+}
+
+/** Count feedback per action across the given chat histories. */
+export function tallyFeedback(histories: ChatMessage[][]): AiFeedbackStats {
+	const stats: AiFeedbackStats = {};
+	for (const history of histories) {
+		for (const m of history) {
+			const feedback = normalizeFeedback(m.feedback);
+			if (!m.action || !feedback) continue;
+			stats[m.action] ??= { used: 0, helpful: 0, "not-helpful": 0 };
+			const counts = stats[m.action];
+			if (feedback.used) counts.used++;
+			if (feedback.rating) counts[feedback.rating]++;
+		}
+	}
+	return stats;
+}
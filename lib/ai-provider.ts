@@ -11,13 +11,60 @@ export interface ProviderSettings {
 	configs: ProviderConfigMap;
 }
 
+export interface ProviderStatus {
+	id: ProviderId;
+	displayName: string;
+	/** Saved config passes validateConfig (no request is sent). */
+	configured: boolean;
+	model: string;
+}
+
+export interface AiMessage {
+	role: "user" | "assistant";
+	content: string;
+}
+
+export interface AiRequest {
+	system: string;
+	/**
+	 * Long, mostly stable meeting context (the transcript). Kept separate from
+	 * `messages` so each provider can place it where its prompt cache hits.
+	 */
+	context?: string;
+	messages: AiMessage[];
+	/** Output token budget; clamped to the model's ceiling by the provider. */
+	maxTokens?: number;
+}
+
 export interface AiProvider {
 	readonly id: ProviderId;
 	readonly displayName: string;
 	call(
-		system: string,
-		messages: { role: "user" | "assistant"; content: string }[],
+		request: AiRequest,
 		config: ProviderConfigMap[ProviderId],
 	): Promise<string>;
 	validateConfig(config: ProviderConfigMap[ProviderId]): string | null;
 }
+
+/**
+ * Make sure the conversation opens with a user turn, so the context is never
+ * attached to an assistant reply (e.g. a history that starts with a note).
+ */
+export function withLeadingUserTurn(messages: AiMessage[]): AiMessage[] {
+	if (messages.length === 0 || messages[0].role === "user") return messages;
+	return [{ role: "user", content: "Here is the meeting so far." }, ...messages];
+}
+
+/**
+ * Prepend the context to the first user message as plain text.
+ * Keeps system + context as a stable prefix for automatic prompt caching.
+ */
+export function mergeContext(request: AiRequest): AiMessage[] {
+	const { context } = request;
+	if (!context || request.messages.length === 0) return request.messages;
+	const [first, ...rest] = withLeadingUserTurn(request.messages);
+	return [
+		{ ...first, content: `${context}\n\n---\n\n${first.content}` },
+		...rest,
+	];
+}
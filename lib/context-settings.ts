@@ -0,0 +1,71 @@
+const STORAGE_KEY = "contextSettings";
+
+/** How much of the transcript is sent to the AI with each request. */
+export type ContextMode = "count" | "minutes";
+
+export interface ContextSettings {
+	mode: ContextMode;
+	/** Most recent utterances to send in "count" mode. */
+	recentUtterances: number;
+	/** Minutes of transcript (back from the latest utterance) in "minutes" mode. */
+	recentMinutes: number;
+	/** Chat messages kept per saved session; older ones are pruned. */
+	maxChatMessages: number;
+	/** Skip chat pruning entirely. */
+	preserveAllChat: boolean;
+}
+
+export const DEFAULT_CONTEXT_SETTINGS: ContextSettings = {
+	mode: "count",
+	recentUtterances: 200,
+	recentMinutes: 10,
+	maxChatMessages: 200,
+	preserveAllChat: false,
+};
+
+export const CONTEXT_LIMITS = {
+	recentUtterances: { min: 10, max: 1000 },
+	recentMinutes: { min: 1, max: 180 },
+	maxChatMessages: { min: 20, max: 2000 },
+} as const;
+
+function clamp(value: unknown, { min, max }: { min: number; max: number }) {
+	const n = Math.round(Number(value));
+	return Number.isFinite(n) ? Math.min(max, Math.max(min, n)) : null;
+}
+
+/** Fill in defaults and pull out-of-range values back into range. */
+export function normalizeContextSettings(
+	settings: Partial<ContextSettings>,
+): ContextSettings {
+	const d = DEFAULT_CONTEXT_SETTINGS;
+	return {
+		mode: settings.mode === "minutes" ? "minutes" : "count",
+		recentUtterances:
+			clamp(settings.recentUtterances, CONTEXT_LIMITS.recentUtterances) ??
+			d.recentUtterances,
+		recentMinutes:
+			clamp(settings.recentMinutes, CONTEXT_LIMITS.recentMinutes) ??
+			d.recentMinutes,
+		maxChatMessages:
+			clamp(settings.maxChatMessages, CONTEXT_LIMITS.maxChatMessages) ??
+			d.maxChatMessages,
+		preserveAllChat: settings.preserveAllChat === true,
+	};
+}
+
+export async function getContextSettings(): Promise<ContextSettings> {
+	const { [STORAGE_KEY]: settings } =
+		await chrome.storage.local.get(STORAGE_KEY);
+	return normalizeContextSettings(
+		(settings as Partial<ContextSettings> | undefined) ?? {},
+	);
+}
+
+export async function setContextSettings(
+	settings: ContextSettings,
+): Promise<void> {
+	await chrome.storage.local.set({
+		[STORAGE_KEY]: normalizeContextSettings(settings),
+	});
+}
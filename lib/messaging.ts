@@ -1,11 +1,17 @@
 import { defineExtensionMessaging } from "@webext-core/messaging";
-import type { ProviderSettings } from "./ai-provider";
+import type { ProviderSettings, ProviderStatus } from "./ai-provider";
+import type { ContextSettings } from "./context-settings";
 import type {
 	AiAction,
+	AiFeedbackStats,
 	AiResponse,
+	AppInfo,
 	ChatMessage,
+	Marker,
 	Session,
 	SessionSummary,
+	SessionsChange,
+	TimeRange,
 	Utterance,
 } from "./types";
 
@@ -28,6 +34,12 @@ interface ProtocolMap {
 	"ai:request"(data: {
 		action: AiAction;
 		utterances: Utterance[];
+		markers?: Marker[];
+		responseLanguage?: string;
+		contextNote?: string;
+		range?: TimeRange;
+		/** Reject unknown actions (default); when false, fall back to Assist. */
+		strict?: boolean;
 	}): AiResponse;
 
 	// Side Panel → Background → Side Panel
@@ -35,11 +47,20 @@ interface ProtocolMap {
 		message: string;
 		utterances: Utterance[];
 		history: ChatMessage[];
+		markers?: Marker[];
+		responseLanguage?: string;
+		contextNote?: string;
+		range?: TimeRange;
 	}): AiResponse;
 
 	// Settings
 	"settings:getProviderSettings"(): ProviderSettings;
 	"settings:setProviderSettings"(settings: ProviderSettings): void;
+	"settings:listProviders"(): ProviderStatus[];
+	// Feedback on AI replies across saved sessions created since `since`
+	"ai:feedback-stats"(data: { since?: string }): AiFeedbackStats;
+	"settings:getContextSettings"(): ContextSettings;
+	"settings:setContextSettings"(settings: ContextSettings): void;
 
 	// Sessions
 	"session:list"(): SessionSummary[];
@@ -47,7 +68,21 @@ interface ProtocolMap {
 	"session:save"(data: Session): void;
 	"session:delete"(data: { id: string }): void;
 	"session:update-title"(data: { id: string; title: string }): void;
-	"session:generate-title"(data: { utterances: Utterance[] }): string;
+	"session:generate-title"(data: {
+		utterances: Utterance[];
+		contextNote?: string;
+	}): string;
+	"session:set-context"(data: { id: string; note: string }): void;
+	"session:remap-speakers"(data: {
+		id: string;
+		mapping: Record<string, string>;
+	}): void;
+	"session:split"(data: { id: string; at: number }): string | null;
+	"session:trim"(data: { id: string; start: number; end: number }): boolean;
+	// SidePanel → Background: session currently being recorded
+	"session:set-live"(data: { id: string | null }): void;
+	// Background → all pages: stored sessions changed
+	"session:changed"(data: SessionsChange): void;
 
 	// Meet URL: Content → Background → SidePanel
 	"meet:url"(data: { url: string }): void;
@@ -56,13 +91,22 @@ interface ProtocolMap {
 	// Meet ended: Background → SidePanel
 	"meet:ended:relay"(): void;
 
+	// Marker shortcut: Background → SidePanel
+	"marker:add:relay"(): void;
+
 	// Meet context: Background → SidePanel
 	"meet:context"(data: { isMeeting: boolean }): void;
 	// SidePanel → Background (init)
 	"sidepanel:init"(): { isMeeting: boolean };
 
+	// About: version and environment summary (no keys or transcript text)
+	"app:info"(): AppInfo;
+
 	// Session viewer: SidePanel → Background
 	"session:open-viewer"(data: { id: string }): void;
 }
 
 export const messenger = defineExtensionMessaging<ProtocolMap>();
+
+/** Port name the side panel keeps open while it is showing. */
+export const SIDEPANEL_PORT = "sidepanel";
@@ -0,0 +1,191 @@
+import { describe, expect, it } from "vitest";
+import {
+	type ContextSettings,
+	DEFAULT_CONTEXT_SETTINGS,
+} from "./context-settings";
+import {
+	CONTEXT_CHAR_BUDGET,
+	buildChatMessages,
+	formatTranscript,
+	sampleToBudget,
+	selectTimeRange,
+	truncateTranscript,
+} from "./prompts";
+import type { Marker, Utterance } from "./types";
+
+const u = (time: string, text = "hello"): Utterance => ({
+	speaker: "Aoi",
+	text,
+	time,
+});
+
+const many = (n: number, time = "10:00:00", text = "hello") =>
+	Array.from({ length: n }, () => u(time, text));
+
+const settings = (partial: Partial<ContextSettings>): ContextSettings => ({
+	...DEFAULT_CONTEXT_SETTINGS,
+	...partial,
+});
+
+describe("truncateTranscript (count mode)", () => {
+	it("keeps everything under the limit", () => {
+		const all = many(5);
+		expect(truncateTranscript(all)).toEqual(all);
+	});
+
+	it("keeps the most recent utterances", () => {
+		const all = [...many(20), u("10:05:00", "last")];
+		const kept = truncateTranscript(all, settings({ recentUtterances: 10 }));
+		expect(kept).toHaveLength(10);
+		expect(kept.at(-1)?.text).toBe("last");
+	});
+});
+
+describe("truncateTranscript (minutes mode)", () => {
+	const minutes = (recentMinutes: number) =>
+		settings({ mode: "minutes", recentMinutes });
+
+	it("keeps utterances within the window of the latest one", () => {
+		const all = [
+			u("10:00:00"),
+			u("10:19:59"),
+			u("10:20:00"),
+			u("10:25:00"),
+			u("10:30:00"),
+		];
+		expect(truncateTranscript(all, minutes(10)).map((x) => x.time)).toEqual([
+			"10:20:00",
+			"10:25:00",
+			"10:30:00",
+		]);
+	});
+
+	it("counts back across midnight", () => {
+		const all = [u("23:50:00"), u("23:55:00"), u("0:02:00")];
+		expect(truncateTranscript(all, minutes(10)).map((x) => x.time)).toEqual([
+			"23:55:00",
+			"0:02:00",
+		]);
+	});
+
+	it("still caps the number of utterances", () => {
+		expect(truncateTranscript(many(1500), minutes(10))).toHaveLength(1000);
+	});
+
+	it("falls back to the count cap when times can't be read", () => {
+		expect(truncateTranscript(many(1500, "?"), minutes(10))).toHaveLength(
+			1000,
+		);
+	});
+});
+
+describe("character budget", () => {
+	it("drops the oldest lines to fit, in both modes", () => {
+		const all = many(100, "10:00:00", "x".repeat(1000));
+		for (const mode of ["count", "minutes"] as const) {
+			const kept = truncateTranscript(all, settings({ mode }));
+			expect(formatTranscript(kept).length).toBeLessThanOrEqual(
+				CONTEXT_CHAR_BUDGET,
+			);
+			// 8 (time) + 3 (speaker) + 1000 (text) + 6 per line
+			expect(kept).toHaveLength(Math.floor(CONTEXT_CHAR_BUDGET / 1017));
+		}
+	});
+});
+
+describe("buildChatMessages", () => {
+	it("leaves out the pruned-messages note", () => {
+		const messages = buildChatMessages(
+			[
+				{ role: "assistant", content: "4 earlier messages…", pruned: 4 },
+				{ role: "user", content: "q" },
+				{ role: "assistant", content: "a" },
+			],
+			"next",
+		);
+		expect(messages).toEqual([
+			{ role: "user", content: "q" },
+			{ role: "assistant", content: "a" },
+			{ role: "user", content: "next" },
+		]);
+	});
+});
+
+describe("selectTimeRange", () => {
+	const all = [
+		u("9:59:59"),
+		u("10:00:00"),
+		u("10:05:30"),
+		u("10:05:59"),
+		u("10:06:00"),
+	];
+	const times = (result: ReturnType<typeof selectTimeRange>) =>
+		typeof result === "string" ? result : result.utterances.map((x) => x.time);
+
+	it("includes both ends, with H:MM ending at the minute's last second", () => {
+		const result = selectTimeRange(all, [], { from: "10:00", to: "10:05" });
+		expect(times(result)).toEqual(["10:00:00", "10:05:30", "10:05:59"]);
+	});
+
+	it("rejects a range whose start is not before its end", () => {
+		const invalid = "Invalid time range. The start must be before the end.";
+		expect(selectTimeRange(all, [], { from: "10:20", to: "10:05" })).toBe(
+			invalid,
+		);
+		expect(
+			selectTimeRange(all, [], { from: "10:05:00", to: "10:05:00" }),
+		).toBe(invalid);
+	});
+
+	it("reads an H:MM range as the whole minute", () => {
+		const result = selectTimeRange(all, [], { from: "10:05", to: "10:05" });
+		expect(times(result)).toEqual(["10:05:30", "10:05:59"]);
+	});
+
+	it("keeps only the markers inside the range", () => {
+		const markers: Marker[] = [
+			{ time: "9:00:00", label: "before", utteranceIndex: 0 },
+			{ time: "10:02:00", label: "inside", utteranceIndex: 1 },
+			{ time: "11:00:00", label: "after", utteranceIndex: 4 },
+		];
+		const range = { from: "10:00", to: "10:05" };
+		const result = selectTimeRange(all, markers, range);
+		expect(typeof result !== "string" && result.markers).toEqual([markers[1]]);
+	});
+
+	it("explains bad or empty ranges", () => {
+		expect(selectTimeRange(all, [], { from: "10", to: "11:00" })).toMatch(
+			/^Invalid time range/,
+		);
+		expect(selectTimeRange(all, [], { from: "12:00", to: "13:00" })).toBe(
+			"Nothing was transcribed between 12:00 and 13:00.",
+		);
+	});
+
+	it("samples long ranges down to the budget", () => {
+		const long = many(2000, "10:30:00", "x".repeat(100));
+		const result = selectTimeRange(long, [], { from: "10:00", to: "11:00" });
+		if (typeof result === "string") throw new Error(result);
+		expect(result.utterances.length).toBeLessThanOrEqual(1000);
+		expect(formatTranscript(result.utterances).length).toBeLessThanOrEqual(
+			CONTEXT_CHAR_BUDGET,
+		);
+	});
+});
+
+describe("sampleToBudget", () => {
+	it("returns short transcripts as-is", () => {
+		const all = many(10);
+		expect(sampleToBudget(all)).toBe(all);
+	});
+
+	it("spreads the sample across the whole span", () => {
+		const all = Array.from({ length: 100 }, (_, i) =>
+			u(`10:${String(i % 60).padStart(2, "0")}:00`, `${i}`.padEnd(50, ".")),
+		);
+		const sample = sampleToBudget(all, 600);
+		expect(formatTranscript(sample).length).toBeLessThanOrEqual(600);
+		expect(sample[0]).toBe(all[0]);
+		expect(all.indexOf(sample.at(-1) as Utterance)).toBeGreaterThan(80);
+	});
+});
@@ -1,18 +1,36 @@
-import type { ChatMessage, Utterance } from "./types";
+import {
+	CONTEXT_LIMITS,
+	type ContextSettings,
+	DEFAULT_CONTEXT_SETTINGS,
+} from "./context-settings";
+import { parseClockTime, secondsOfDay } from "./time";
+import type {
+	AiAction,
+	ChatMessage,
+	Marker,
+	TimeRange,
+	Utterance,
+} from "./types";
 
 const SYSTEM_BASE = `You are Kanpe, an AI meeting assistant for Google Meet.
 You are given a transcript of the ongoing meeting.
 Respond concisely and actionably. Use the same language as the transcript.`;
 
-export const PROMPTS = {
+interface ActionPrompt {
+	system: string;
+	instruction: string;
+	/** Output budget; falls back to the provider default when omitted. */
+	maxTokens?: number;
+}
+
+export const PROMPTS: Record<AiAction, ActionPrompt> = {
 	recap: {
 		system: `${SYSTEM_BASE}
 Your task is to summarize the meeting so far.
 - Highlight key topics discussed
 - Note any decisions made
 - Keep it concise (3-5 bullet points)`,
-		userTemplate: (transcript: string) =>
-			`Here is the meeting transcript so far:\n\n${transcript}\n\nPlease provide a recap.`,
+		instruction: "Please provide a recap.",
 	},
 	assist: {
 		system: `${SYSTEM_BASE}
@@ -20,8 +38,7 @@ Your task is to suggest what the user should say next.
 - Consider the flow of the conversation
 - Provide 2-3 concrete suggestions
 - Each suggestion should be a complete sentence ready to speak`,
-		userTemplate: (transcript: string) =>
-			`Here is the meeting transcript so far:\n\n${transcript}\n\nWhat should I say next?`,
+		instruction: "What should I say next?",
 	},
 	question: {
 		system: `${SYSTEM_BASE}
@@ -29,8 +46,7 @@ Your task is to suggest relevant questions to ask.
 - Identify gaps or unclear points in the discussion
 - Provide 2-3 specific questions
 - Questions should move the meeting forward productively`,
-		userTemplate: (transcript: string) =>
-			`Here is the meeting transcript so far:\n\n${transcript}\n\nWhat questions should I ask?`,
+		instruction: "What questions should I ask?",
 	},
 	action: {
 		system: `${SYSTEM_BASE}
@@ -39,10 +55,50 @@ Your task is to extract action items and decisions.
 - Include any deadlines mentioned
 - Separate decisions from action items
 - Format as a clear checklist`,
-		userTemplate: (transcript: string) =>
-			`Here is the meeting transcript so far:\n\n${transcript}\n\nPlease extract action items and decisions.`,
+		instruction: "Please extract action items and decisions.",
+	},
+	objection: {
+		system: `${SYSTEM_BASE}
+The other party has just pushed back or raised an objection.
+Your task is to help the user respond.
+- Focus on the last few turns and restate their concern in one line
+- Provide 2-3 calm, concrete responses that address that concern
+- Each response should be a complete sentence ready to speak`,
+		instruction: "How should I respond to this pushback?",
+		maxTokens: 600,
 	},
-} as const;
+	explain: {
+		system: `${SYSTEM_BASE}
+Your task is to re-explain the concept that was just discussed.
+- Focus on the last few turns
+- Use plain words the user can say out loud
+- At most 4 sentences`,
+		instruction: "Explain what was just discussed in simpler terms.",
+		maxTokens: 400,
+	},
+};
+
+/** Accepts "xx" or "xx-YY" language codes (e.g. "en", "pt-BR"). */
+export function isValidLanguageCode(code: string): boolean {
+	return /^[a-z]{2}(-[A-Z]{2})?$/.test(code);
+}
+
+/** Tell the model the transcript is a deliberate slice of the meeting. */
+export function withTimeRange(system: string, range?: TimeRange): string {
+	if (!range) return system;
+	return `${system}
+The transcript covers only ${range.from} to ${range.to}, as the user asked. Answer about that part of the meeting only and do not guess at what was said outside it.`;
+}
+
+/** Override the default "same language as the transcript" rule. */
+export function withResponseLanguage(
+	system: string,
+	language?: string,
+): string {
+	if (!language) return system;
+	return `${system}
+Respond in the language with code "${language}", regardless of the transcript language.`;
+}
 
 export const CHAT_SYSTEM = `${SYSTEM_BASE}
 You are having a conversation with the meeting participant.
@@ -57,8 +113,7 @@ Given a meeting transcript, generate a short, descriptive title for the meeting.
 - Use the same language as the transcript
 - Do NOT include dates, times, or meeting codes
 - Return ONLY the title text, nothing else`,
-	userTemplate: (transcript: string) =>
-		`Here is the meeting transcript:\n\n${transcript}\n\nGenerate a concise title for this meeting.`,
+	instruction: "Generate a concise title for this meeting.",
 };
 
 export function formatTranscript(utterances: Utterance[]): string {
@@ -67,39 +122,177 @@ export function formatTranscript(utterances: Utterance[]): string {
 		.join("\n");
 }
 
+/** The caption a marker was dropped on, as `Speaker: "text"`. */
+export function markerQuote(
+	marker: Marker,
+	utterances: Utterance[],
+): string | null {
+	const u = utterances[marker.utteranceIndex];
+	return u ? `${u.speaker}: "${u.text}"` : null;
+}
+
+/**
+ * Render user-flagged markers as a section appended to the transcript so the
+ * model weights those moments. Each marker quotes the caption it was dropped on.
+ */
+export function formatMarkers(
+	markers: Marker[] | undefined,
+	utterances: Utterance[],
+): string {
+	if (!markers || markers.length === 0) return "";
+	const lines = markers.map((m) => {
+		const label = m.label || "(no label)";
+		const quote = markerQuote(m, utterances);
+		return `- [${m.time}] ${label}${quote ? ` — ${quote}` : ""}`;
+	});
+	return `\n\nThe user flagged these moments as important:\n${lines.join("\n")}`;
+}
+
+/** Upper bound on transcript characters per request, whatever the settings. */
+export const CONTEXT_CHAR_BUDGET = 60_000;
+
+// Length of an utterance's line in formatTranscript, newline included
+function lineLength(u: Utterance): number {
+	return u.time.length + u.speaker.length + u.text.length + 6;
+}
+
+/** Drop the oldest utterances until the formatted transcript fits `budget`. */
+export function fitToBudget(
+	utterances: Utterance[],
+	budget = CONTEXT_CHAR_BUDGET,
+): Utterance[] {
+	let total = 0;
+	let start = utterances.length;
+	while (start > 0) {
+		const next = total + lineLength(utterances[start - 1]);
+		if (next > budget) break;
+		total = next;
+		start--;
+	}
+	return utterances.slice(start);
+}
+
+function recentUtterances(
+	utterances: Utterance[],
+	settings: ContextSettings,
+): Utterance[] {
+	if (settings.mode === "count") {
+		return utterances.slice(-settings.recentUtterances);
+	}
+
+	// Busy meetings can pack a lot into a few minutes, so the largest count
+	// setting still applies
+	const maxCount = CONTEXT_LIMITS.recentUtterances.max;
+	const last = secondsOfDay(utterances.at(-1)?.time ?? "");
+	if (last === null) return utterances.slice(-maxCount);
+	const windowSeconds = settings.recentMinutes * 60;
+	let start = utterances.length - 1;
+	while (start > 0 && utterances.length - start < maxCount) {
+		const at = secondsOfDay(utterances[start - 1].time);
+		if (at !== null && (last - at + 86400) % 86400 > windowSeconds) break;
+		start--;
+	}
+	return utterances.slice(start);
+}
+
+/**
+ * Pick the tail of the transcript sent to the AI.
+ * In "minutes" mode, utterances are kept while they fall within the window
+ * counted back from the latest one (wrapping past midnight). Either way the
+ * result is cut to CONTEXT_CHAR_BUDGET.
+ */
 export function truncateTranscript(
 	utterances: Utterance[],
-	maxCount = 200,
+	settings: ContextSettings = DEFAULT_CONTEXT_SETTINGS,
 ): Utterance[] {
-	if (utterances.length <= maxCount) return utterances;
-	return utterances.slice(-maxCount);
+	return fitToBudget(recentUtterances(utterances, settings));
 }
 
-export function buildChatMessages(
+/**
+ * Keep an even sample of `utterances` across their whole span so the result
+ * fits both the utterance cap and `budget`. Unlike fitToBudget, early parts
+ * are not dropped wholesale.
+ */
+export function sampleToBudget(
+	utterances: Utterance[],
+	budget = CONTEXT_CHAR_BUDGET,
+): Utterance[] {
+	const size = (list: Utterance[]) =>
+		list.reduce((sum, u) => sum + lineLength(u), 0);
+	const total = size(utterances);
+	const maxCount = CONTEXT_LIMITS.recentUtterances.max;
+	if (total <= budget && utterances.length <= maxCount) return utterances;
+
+	let keep = Math.min(
+		maxCount,
+		Math.floor((utterances.length * budget) / total),
+	);
+	// Lines vary in length, so shrink until the sample actually fits
+	for (; keep > 0; keep--) {
+		const step = utterances.length / keep;
+		const sample = Array.from(
+			{ length: keep },
+			(_, i) => utterances[Math.floor(i * step)],
+		);
+		if (size(sample) <= budget) return sample;
+	}
+	return [];
+}
+
+/**
+ * Pick the utterances and markers between `range.from` and `range.to`, or
+ * return an error message. The start must be before the end. Long ranges are
+ * sampled down with sampleToBudget.
+ */
+export function selectTimeRange(
+	utterances: Utterance[],
+	markers: Marker[] | undefined,
+	range: TimeRange,
+): { utterances: Utterance[]; markers: Marker[] } | string {
+	const from = parseClockTime(range.from);
+	const to = parseClockTime(range.to, true);
+	if (from === null || to === null) {
+		return 'Invalid time range. Use times like "10:05" or "10:05:30".';
+	}
+	if (from >= to) {
+		return "Invalid time range. The start must be before the end.";
+	}
+	const inRange = (time: string) => {
+		const at = secondsOfDay(time);
+		return at !== null && at >= from && at <= to;
+	};
+
+	const selected = utterances.filter((u) => inRange(u.time));
+	if (selected.length === 0) {
+		return `Nothing was transcribed between ${range.from} and ${range.to}.`;
+	}
+	return {
+		utterances: sampleToBudget(selected),
+		markers: (markers ?? []).filter((m) => inRange(m.time)),
+	};
+}
+
+/**
+ * Wrap the formatted transcript as the request context.
+ * Providers send it ahead of the conversation so it can be prompt-cached.
+ */
+export function buildTranscriptContext(
 	transcript: string,
+	contextNote?: string,
+): string {
+	const note = contextNote?.trim();
+	const header = note ? `About this meeting (from the user): ${note}\n\n` : "";
+	return `${header}Meeting transcript:\n\n${transcript}`;
+}
+
+export function buildChatMessages(
 	history: ChatMessage[],
 	newMessage: string,
 ): { role: "user" | "assistant"; content: string }[] {
-	const messages: { role: "user" | "assistant"; content: string }[] = [];
-
-	if (history.length === 0) {
-		messages.push({
-			role: "user",
-			content: `Meeting transcript:\n\n${transcript}\n\n---\n\n${newMessage}`,
-		});
-	} else {
-		messages.push({
-			role: "user",
-			content: `Meeting transcript:\n\n${transcript}\n\n---\n\n${history[0].content}`,
-		});
-		for (let i = 1; i < history.length; i++) {
-			messages.push({
-				role: history[i].role,
-				content: history[i].content,
-			});
-		}
-		messages.push({ role: "user", content: newMessage });
-	}
-
-	return messages;
+	return [
+		...history
+			.filter((m) => !m.pruned)
+			.map((m) => ({ role: m.role, content: m.content })),
+		{ role: "user", content: newMessage },
+	];
 }
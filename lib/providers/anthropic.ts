@@ -1,23 +1,67 @@
-import type { AiProvider, ProviderConfigMap } from "../ai-provider";
+import {
+	type AiProvider,
+	type AiRequest,
+	type ProviderConfigMap,
+	withLeadingUserTurn,
+} from "../ai-provider";
+import {
+	ANTHROPIC_OUTPUT_LIMITS,
+	DEFAULT_MAX_TOKENS,
+	clampMaxTokens,
+} from "./limits";
 
 const ANTHROPIC_API_URL = "https://api.anthropic.com/v1/messages";
 
+interface AnthropicTextBlock {
+	type: "text";
+	text: string;
+	cache_control?: { type: "ephemeral" };
+}
+
 interface AnthropicRequest {
 	model: string;
 	max_tokens: number;
 	system?: string;
-	messages: { role: "user" | "assistant"; content: string }[];
+	messages: {
+		role: "user" | "assistant";
+		content: string | AnthropicTextBlock[];
+	}[];
 }
 
 interface AnthropicResponse {
 	content: Array<{ type: "text"; text: string }>;
 }
 
+/**
+ * Put the transcript in its own block on the first user turn and mark it as a
+ * cache breakpoint, so repeated actions and chat turns over the same
+ * transcript are billed at the cached rate.
+ */
+function buildMessages(request: AiRequest): AnthropicRequest["messages"] {
+	const { context } = request;
+	if (!context || request.messages.length === 0) return request.messages;
+	const [first, ...rest] = withLeadingUserTurn(request.messages);
+	return [
+		{
+			role: first.role,
+			content: [
+				{
+					type: "text",
+					text: context,
+					cache_control: { type: "ephemeral" },
+				},
+				{ type: "text", text: first.content },
+			],
+		},
+		...rest,
+	];
+}
+
 export const anthropicProvider: AiProvider = {
 	id: "anthropic",
 	displayName: "Anthropic",
 
-	async call(system, messages, config) {
+	async call(request, config) {
 		const { apiKey, model } = config as ProviderConfigMap["anthropic"];
 
 		const response = await fetch(ANTHROPIC_API_URL, {
@@ -30,9 +74,13 @@ export const anthropicProvider: AiProvider = {
 			},
 			body: JSON.stringify({
 				model,
-				max_tokens: 2048,
-				system,
-				messages,
+				max_tokens: clampMaxTokens(
+					request.maxTokens ?? DEFAULT_MAX_TOKENS,
+					model,
+					ANTHROPIC_OUTPUT_LIMITS,
+				),
+				system: request.system,
+				messages: buildMessages(request),
 			} satisfies AnthropicRequest),
 		});
 
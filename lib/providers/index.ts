@@ -1,4 +1,9 @@
-import type { AiProvider, ProviderId } from "../ai-provider";
+import type {
+	AiProvider,
+	ProviderId,
+	ProviderSettings,
+	ProviderStatus,
+} from "../ai-provider";
 import { anthropicProvider } from "./anthropic";
 import { ollamaProvider } from "./ollama";
 import { openaiProvider } from "./openai";
@@ -13,4 +18,18 @@ export function getProvider(id: ProviderId): AiProvider {
 	return providers[id];
 }
 
+export function listProviderStatuses(
+	settings: ProviderSettings,
+): ProviderStatus[] {
+	return Object.values(providers).map((provider) => {
+		const config = settings.configs[provider.id];
+		return {
+			id: provider.id,
+			displayName: provider.displayName,
+			configured: provider.validateConfig(config) === null,
+			model: config.model,
+		};
+	});
+}
+
 export { ApiError, classifyApiError } from "./anthropic";
@@ -0,0 +1,42 @@
+export const DEFAULT_MAX_TOKENS = 2048;
+
+// Max output tokens per model family. Matched by longest prefix so dated
+// snapshots (e.g. "claude-3-5-haiku-20241022") inherit their family's limit.
+export const ANTHROPIC_OUTPUT_LIMITS: Record<string, number> = {
+	"claude-opus-4": 32000,
+	"claude-sonnet-4": 64000,
+	"claude-haiku-4-5": 64000,
+	"claude-3-7-sonnet": 64000,
+	"claude-3-5-sonnet": 8192,
+	"claude-3-5-haiku": 8192,
+	"claude-3-opus": 4096,
+	"claude-3-haiku": 4096,
+};
+
+export const OPENAI_OUTPUT_LIMITS: Record<string, number> = {
+	"gpt-4.1": 32768,
+	"gpt-4o": 16384,
+	"gpt-4o-mini": 16384,
+	"gpt-4-turbo": 4096,
+	"gpt-4": 8192,
+	"gpt-3.5-turbo": 4096,
+};
+
+/**
+ * Clamp a requested output budget to the model's ceiling.
+ * Unknown models pass through unchanged.
+ */
+export function clampMaxTokens(
+	requested: number,
+	model: string,
+	limits: Record<string, number>,
+): number {
+	const family = Object.keys(limits)
+		.filter((prefix) => model.startsWith(prefix))
+		.sort((a, b) => b.length - a.length)[0];
+	if (!family || requested <= limits[family]) return requested;
+	console.warn(
+		`[kanpe] max_tokens ${requested} exceeds ${model} limit, clamping to ${limits[family]}`,
+	);
+	return limits[family];
+}
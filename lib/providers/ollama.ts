@@ -1,4 +1,8 @@
-import type { AiProvider, ProviderConfigMap } from "../ai-provider";
+import {
+	type AiProvider,
+	type ProviderConfigMap,
+	mergeContext,
+} from "../ai-provider";
 import { ApiError } from "./anthropic";
 
 interface OllamaRequest {
@@ -15,7 +19,7 @@ export const ollamaProvider: AiProvider = {
 	id: "ollama",
 	displayName: "Ollama",
 
-	async call(system, messages, config) {
+	async call(request, config) {
 		const { model, baseUrl } = config as ProviderConfigMap["ollama"];
 		const url = `${baseUrl.replace(/\/+$/, "")}/api/chat`;
 
@@ -24,7 +28,10 @@ export const ollamaProvider: AiProvider = {
 			headers: { "Content-Type": "application/json" },
 			body: JSON.stringify({
 				model,
-				messages: [{ role: "system", content: system }, ...messages],
+				messages: [
+					{ role: "system", content: request.system },
+					...mergeContext(request),
+				],
 				stream: false,
 			} satisfies OllamaRequest),
 		});
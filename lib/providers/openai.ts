@@ -1,5 +1,14 @@
-import type { AiProvider, ProviderConfigMap } from "../ai-provider";
+import {
+	type AiProvider,
+	type ProviderConfigMap,
+	mergeContext,
+} from "../ai-provider";
 import { ApiError } from "./anthropic";
+import {
+	DEFAULT_MAX_TOKENS,
+	OPENAI_OUTPUT_LIMITS,
+	clampMaxTokens,
+} from "./limits";
 
 interface OpenAiRequest {
 	model: string;
@@ -15,7 +24,7 @@ export const openaiProvider: AiProvider = {
 	id: "openai",
 	displayName: "OpenAI",
 
-	async call(system, messages, config) {
+	async call(request, config) {
 		const { apiKey, model, baseUrl } = config as ProviderConfigMap["openai"];
 		const url = `${baseUrl.replace(/\/+$/, "")}/v1/chat/completions`;
 
@@ -27,8 +36,16 @@ export const openaiProvider: AiProvider = {
 			},
 			body: JSON.stringify({
 				model,
-				messages: [{ role: "system", content: system }, ...messages],
-				max_tokens: 2048,
+				// System prompt + transcript first so the prefix is cacheable
+				messages: [
+					{ role: "system", content: request.system },
+					...mergeContext(request),
+				],
+				max_tokens: clampMaxTokens(
+					request.maxTokens ?? DEFAULT_MAX_TOKENS,
+					model,
+					OPENAI_OUTPUT_LIMITS,
+				),
 			} satisfies OpenAiRequest),
 		});
 
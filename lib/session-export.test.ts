@@ -0,0 +1,238 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import { FIXTURE_SESSION } from "./__fixtures__/session";
+import {
+	SESSION_EXPORT_VERSION,
+	escapeCsvField,
+	exportSession,
+	parseSessionExport,
+	toCsv,
+	wrapText,
+} from "./session-export";
+import type { Session } from "./types";
+
+describe("escapeCsvField", () => {
+	it("leaves plain and empty fields as-is", () => {
+		expect(escapeCsvField("hello world")).toBe("hello world");
+		expect(escapeCsvField("")).toBe("");
+	});
+
+	it("quotes fields with commas", () => {
+		expect(escapeCsvField("a,b")).toBe('"a,b"');
+	});
+
+	it("doubles embedded quotes", () => {
+		expect(escapeCsvField('say "hi"')).toBe('"say ""hi"""');
+	});
+
+	it("quotes fields with CR, LF, or CRLF", () => {
+		expect(escapeCsvField("a\nb")).toBe('"a\nb"');
+		expect(escapeCsvField("a\rb")).toBe('"a\rb"');
+		expect(escapeCsvField("a\r\nb")).toBe('"a\r\nb"');
+	});
+
+	it("neutralizes formula prefixes", () => {
+		expect(escapeCsvField("=SUM(A1:A9)")).toBe("'=SUM(A1:A9)");
+		expect(escapeCsvField("+1 555 0100")).toBe("'+1 555 0100");
+		expect(escapeCsvField("-2")).toBe("'-2");
+		expect(escapeCsvField("@here")).toBe("'@here");
+		expect(escapeCsvField('=HYPERLINK("x",1)')).toBe(
+			`"'=HYPERLINK(""x"",1)"`,
+		);
+	});
+
+	it("only checks the first character for formulas", () => {
+		expect(escapeCsvField("1 + 1 = 2")).toBe("1 + 1 = 2");
+	});
+});
+
+describe("toCsv", () => {
+	it("joins rows with CRLF and ends with a line break", () => {
+		expect(
+			toCsv([
+				["time", "text"],
+				["10:00:00", "a,b"],
+			]),
+		).toBe('time,text\r\n10:00:00,"a,b"\r\n');
+	});
+
+	it("keeps empty fields as empty columns", () => {
+		expect(toCsv([["", "x", ""]])).toBe(",x,\r\n");
+	});
+
+	it("prefixes a BOM when asked", () => {
+		expect(toCsv([["a"]], { bom: true })).toBe("\u{FEFF}a\r\n");
+	});
+});
+
+describe("JSON export", () => {
+	beforeEach(() => {
+		vi.useFakeTimers();
+		vi.setSystemTime(new Date("2026-10-02T00:00:00.000Z"));
+		vi.stubGlobal("chrome", {
+			runtime: { getManifest: () => ({ version: "1.2.3" }) },
+		});
+	});
+
+	afterEach(() => {
+		vi.useRealTimers();
+		vi.unstubAllGlobals();
+	});
+
+	const render = (session: Session) => exportSession(session, "json").content;
+
+	it("writes every field in a fixed order", () => {
+		const expected = {
+			format: "kanpe-session",
+			version: SESSION_EXPORT_VERSION,
+			exportedAt: "2026-10-02T00:00:00.000Z",
+			appVersion: "1.2.3",
+			session: {
+				id: "session-1",
+				title: "Release sync",
+				createdAt: "2026-10-01T01:00:00.000Z",
+				meetUrl: "https://meet.google.com/abc-defg-hij",
+				contextNote: "Weekly release meeting",
+				responseLanguage: "en",
+				utterances: [
+					{ time: "10:01:15", speaker: "Aoi", text: "Let's ship on Friday." },
+					{ time: "10:02:40", speaker: "Ren", text: 'Agreed, "Friday" it is.' },
+				],
+				markers: [{ time: "10:02:41", label: "Decision", utteranceIndex: 1 }],
+				chatHistory: [
+					{
+						role: "assistant",
+						content: "[2 earlier messages were pruned]",
+						pruned: 2,
+					},
+					{ role: "user", content: "What did we decide?" },
+					{
+						role: "assistant",
+						content: "Ship on Friday.",
+						language: "en",
+						action: "recap",
+						feedback: { used: true, rating: "helpful" },
+						range: { from: "10:00", to: "10:05" },
+					},
+				],
+			},
+		};
+		// Compared as text so key order is part of the snapshot
+		expect(render(FIXTURE_SESSION)).toBe(
+			`${JSON.stringify(expected, null, 2)}\n`,
+		);
+	});
+
+	it("round-trips through parseSessionExport", () => {
+		expect(parseSessionExport(render(FIXTURE_SESSION))).toEqual(
+			FIXTURE_SESSION,
+		);
+	});
+});
+
+describe("parseSessionExport", () => {
+	const envelope = (session: unknown, version = SESSION_EXPORT_VERSION) =>
+		JSON.stringify({
+			format: "kanpe-session",
+			version,
+			exportedAt: "2026-10-02T00:00:00.000Z",
+			appVersion: "1.2.3",
+			session,
+		});
+
+	const withField = (patch: Record<string, unknown>) =>
+		envelope({ ...FIXTURE_SESSION, ...patch });
+
+	it("accepts version 1 exports and bare sessions", () => {
+		const { id, title, createdAt, utterances } = FIXTURE_SESSION;
+		const v1 = { id, title, createdAt, utterances };
+		const expected = { ...v1, markers: [], chatHistory: [] };
+		expect(parseSessionExport(envelope(v1, 1))).toEqual(expected);
+		expect(parseSessionExport(JSON.stringify(v1))).toEqual(expected);
+	});
+
+	it("rejects newer versions and other JSON", () => {
+		expect(() =>
+			parseSessionExport(envelope(FIXTURE_SESSION, SESSION_EXPORT_VERSION + 1)),
+		).toThrow(/newer than this app supports/);
+		expect(() => parseSessionExport('{"format":"other"}')).toThrow(
+			"Not a Kanpe session export.",
+		);
+		expect(() => parseSessionExport("not json")).toThrow(
+			"Not a valid JSON file.",
+		);
+	});
+
+	it("names the field that is missing or malformed", () => {
+		expect(() => parseSessionExport(withField({ id: 1 }))).toThrow(
+			"Invalid session export at session.id.",
+		);
+		expect(() =>
+			parseSessionExport(withField({ utterances: undefined })),
+		).toThrow("Invalid session export at session.utterances.");
+		expect(() =>
+			parseSessionExport(withField({ utterances: [{ time: "1:00:00" }] })),
+		).toThrow("Invalid session export at session.utterances[0].speaker.");
+		expect(() => parseSessionExport(withField({ markers: [null] }))).toThrow(
+			"Invalid session export at session.markers[0].",
+		);
+		expect(() =>
+			parseSessionExport(
+				withField({ chatHistory: [{ role: "system", content: "" }] }),
+			),
+		).toThrow("Invalid session export at session.chatHistory[0].role.");
+		expect(() =>
+			parseSessionExport(
+				withField({
+					chatHistory: [{ role: "user", content: "", range: { from: 1 } }],
+				}),
+			),
+		).toThrow("Invalid session export at session.chatHistory[0].range.from.");
+	});
+
+	it("reads the single-string feedback of older sessions", () => {
+		const session = parseSessionExport(
+			withField({
+				chatHistory: [
+					{
+						role: "assistant",
+						content: "",
+						action: "assist",
+						feedback: "used",
+					},
+				],
+			}),
+		);
+		expect(session.chatHistory[0].feedback).toEqual({ used: true });
+	});
+});
+
+describe("wrapText", () => {
+	it("wraps at word boundaries and indents continuation lines", () => {
+		expect(wrapText("alpha beta gamma", 11, 2)).toBe("alpha beta\n  gamma");
+	});
+
+	it("splits words longer than a line", () => {
+		const lengths = wrapText("x".repeat(250), 100)
+			.split("\n")
+			.map((l) => l.length);
+		expect(lengths).toEqual([100, 100, 50]);
+	});
+
+	it("keeps indented lines within the width", () => {
+		const url = `https://example.com/${"a".repeat(60)}`;
+		const lines = wrapText(`see ${url}`, 20, 4).split("\n");
+		for (const line of lines) expect(line.length).toBeLessThanOrEqual(20);
+		expect(lines[0]).toBe(`see ${url.slice(0, 16)}`);
+		expect(lines.map((l) => l.trim()).join("")).toBe(`see ${url}`);
+	});
+});
+
+describe("chat CSV", () => {
+	it("includes the action and skips the pruned-messages note", () => {
+		expect(exportSession(FIXTURE_SESSION, "chat-csv").content).toBe(
+			"role,action,content\r\n" +
+				"user,,What did we decide?\r\n" +
+				"assistant,recap,Ship on Friday.\r\n",
+		);
+	});
+});
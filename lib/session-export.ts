@@ -0,0 +1,459 @@
+import { AI_ACTIONS } from "./ai-actions";
+import { normalizeFeedback } from "./ai-feedback";
+import { markerQuote } from "./prompts";
+import type {
+	ChatMessage,
+	Marker,
+	Session,
+	TimeRange,
+	Utterance,
+} from "./types";
+
+export type ExportFormat = "json" | "csv" | "chat-csv" | "txt" | "html";
+
+export interface ExportOptions {
+	/** Prefix a UTF-8 BOM so Excel on Windows detects the encoding. */
+	bom?: boolean;
+}
+
+export const EXPORT_FORMATS: { id: ExportFormat; label: string }[] = [
+	{ id: "json", label: "Session (JSON)" },
+	{ id: "csv", label: "Transcript (CSV)" },
+	{ id: "chat-csv", label: "Chat (CSV)" },
+	{ id: "txt", label: "Plain text" },
+	{ id: "html", label: "HTML" },
+];
+
+const BOM = "\u{FEFF}";
+const TXT_WIDTH = 100;
+
+// ── Render model ──
+// Every format renders from this so they can't drift apart.
+
+interface RenderEntry {
+	anchor: string;
+	time: string;
+	speaker: string;
+	text: string;
+}
+
+interface RenderMarker {
+	time: string;
+	label: string;
+	/** Anchor of the utterance the marker was dropped on, if any. */
+	anchor: string | null;
+	/** That utterance, quoted the same way as in AI prompts. */
+	quote: string | null;
+}
+
+interface RenderModel {
+	title: string;
+	startedAt: string;
+	entries: RenderEntry[];
+	markers: RenderMarker[];
+	chat: ChatMessage[];
+}
+
+function buildRenderModel(session: Session): RenderModel {
+	const entries = session.utterances.map((u, i) => ({
+		anchor: `u${i + 1}`,
+		time: u.time,
+		speaker: u.speaker,
+		text: u.text,
+	}));
+	return {
+		title: session.title,
+		startedAt: new Date(session.createdAt).toLocaleString(),
+		entries,
+		markers: (session.markers ?? []).map((m) => ({
+			time: m.time,
+			label: m.label || "Marker",
+			anchor: entries[m.utteranceIndex]?.anchor ?? null,
+			quote: markerQuote(m, session.utterances),
+		})),
+		chat: session.chatHistory,
+	};
+}
+
+// ── JSON ──
+
+const SESSION_EXPORT_FORMAT = "kanpe-session";
+
+/**
+ * Bump whenever the exported session shape changes.
+ * 2: chat messages keep their action, feedback, range and pruned count.
+ */
+export const SESSION_EXPORT_VERSION = 2;
+
+export interface SessionExport {
+	format: typeof SESSION_EXPORT_FORMAT;
+	version: number;
+	exportedAt: string;
+	appVersion: string;
+	session: Session;
+}
+
+// Readers validate one field and return its normalized value, undefined if
+// it is absent, or throw with the path of the bad value.
+type Reader = (value: unknown, path: string) => unknown;
+
+// Every field of a stored type, in output order. Keyed by the interface so a
+// new field has to be listed here before this compiles.
+type Fields<T> = Record<keyof T, Reader>;
+
+function invalid(path: string): never {
+	throw new Error(`Invalid session export at ${path}.`);
+}
+
+const readString: Reader = (v, path) =>
+	typeof v === "string" ? v : invalid(path);
+
+const readInteger: Reader = (v, path) =>
+	Number.isInteger(v) ? v : invalid(path);
+
+const oneOf =
+	(...values: unknown[]): Reader =>
+	(v, path) =>
+		values.includes(v) ? v : invalid(path);
+
+const optional =
+	(read: Reader): Reader =>
+	(v, path) =>
+		v === undefined ? undefined : read(v, path);
+
+const required =
+	(read: Reader): Reader =>
+	(v, path) =>
+		v === undefined ? invalid(path) : read(v, path);
+
+function readRecord<T>(
+	fields: Fields<T>,
+	value: unknown,
+	path: string,
+): T {
+	if (typeof value !== "object" || value === null || Array.isArray(value)) {
+		invalid(path);
+	}
+	const source = value as Record<string, unknown>;
+	const out: Record<string, unknown> = {};
+	for (const [key, read] of Object.entries(fields) as [string, Reader][]) {
+		const v = read(source[key], `${path}.${key}`);
+		if (v !== undefined) out[key] = v;
+	}
+	return out as T;
+}
+
+const record =
+	<T>(fields: Fields<T>): Reader =>
+	(v, path) =>
+		readRecord(fields, v, path);
+
+// Lists that older exports may omit read as empty
+const list =
+	<T>(fields: Fields<T>): Reader =>
+	(v, path) => {
+		if (v === undefined) return [];
+		if (!Array.isArray(v)) invalid(path);
+		return v.map((item, i) => readRecord(fields, item, `${path}[${i}]`));
+	};
+
+const UTTERANCE_FIELDS: Fields<Utterance> = {
+	time: readString,
+	speaker: readString,
+	text: readString,
+};
+
+const MARKER_FIELDS: Fields<Marker> = {
+	time: readString,
+	label: readString,
+	utteranceIndex: readInteger,
+};
+
+const TIME_RANGE_FIELDS: Fields<TimeRange> = {
+	from: readString,
+	to: readString,
+};
+
+const CHAT_MESSAGE_FIELDS: Fields<ChatMessage> = {
+	role: oneOf("user", "assistant"),
+	content: readString,
+	language: optional(readString),
+	action: optional(oneOf("chat", ...AI_ACTIONS.map((a) => a.id))),
+	// Unreadable feedback is dropped rather than failing the whole import
+	feedback: (v) => normalizeFeedback(v),
+	range: optional(record(TIME_RANGE_FIELDS)),
+	pruned: optional(readInteger),
+};
+
+const SESSION_FIELDS: Fields<Session> = {
+	id: readString,
+	title: readString,
+	createdAt: readString,
+	meetUrl: optional(readString),
+	contextNote: optional(readString),
+	responseLanguage: optional(readString),
+	utterances: required(list(UTTERANCE_FIELDS)),
+	markers: list(MARKER_FIELDS),
+	chatHistory: list(CHAT_MESSAGE_FIELDS),
+};
+
+// The output order is fixed by the field lists, not by however the stored
+// object happened to be built.
+function normalizeSession(session: unknown): Session {
+	return readRecord(SESSION_FIELDS, session, "session");
+}
+
+function renderJson(session: Session): string {
+	const envelope: SessionExport = {
+		format: SESSION_EXPORT_FORMAT,
+		version: SESSION_EXPORT_VERSION,
+		exportedAt: new Date().toISOString(),
+		appVersion: chrome.runtime.getManifest().version,
+		session: normalizeSession(session),
+	};
+	return `${JSON.stringify(envelope, null, 2)}\n`;
+}
+
+/**
+ * Parse a JSON export back into a session.
+ * Accepts the versioned envelope of any version up to the current one, as
+ * well as a bare session object. Every field is validated.
+ */
+export function parseSessionExport(text: string): Session {
+	let data: unknown;
+	try {
+		data = JSON.parse(text);
+	} catch {
+		throw new Error("Not a valid JSON file.");
+	}
+	if (typeof data !== "object" || data === null) {
+		throw new Error("Not a Kanpe session export.");
+	}
+
+	let session: unknown = data;
+	if ("format" in data) {
+		const envelope = data as Partial<SessionExport>;
+		if (envelope.format !== SESSION_EXPORT_FORMAT || !envelope.session) {
+			throw new Error("Not a Kanpe session export.");
+		}
+		if ((envelope.version ?? 0) > SESSION_EXPORT_VERSION) {
+			throw new Error(
+				`Export version ${envelope.version} is newer than this app supports.`,
+			);
+		}
+		session = envelope.session;
+	}
+	return normalizeSession(session);
+}
+
+// ── CSV ──
+
+// Spreadsheet apps evaluate cells starting with these as formulas
+const FORMULA_PREFIX = /^[=+\-@\t\r]/;
+
+/**
+ * Quote a field per RFC 4180 when it contains a comma, quote, or line break.
+ * Fields that would be read as a formula get a leading apostrophe first.
+ */
+export function escapeCsvField(value: string): string {
+	const safe = FORMULA_PREFIX.test(value) ? `'${value}` : value;
+	if (!/[",\r\n]/.test(safe)) return safe;
+	return `"${safe.replace(/"/g, '""')}"`;
+}
+
+export function toCsv(rows: string[][], options: ExportOptions = {}): string {
+	const body = rows
+		.map((row) => row.map(escapeCsvField).join(","))
+		.join("\r\n");
+	return `${options.bom ? BOM : ""}${body}\r\n`;
+}
+
+function renderTranscriptCsv(model: RenderModel, options: ExportOptions) {
+	return toCsv(
+		[
+			["time", "speaker", "text"],
+			...model.entries.map((e) => [e.time, e.speaker, e.text]),
+		],
+		options,
+	);
+}
+
+// The pruned-messages note is not a real reply, so it gets no row
+function renderChatCsv(model: RenderModel, options: ExportOptions) {
+	return toCsv(
+		[
+			["role", "action", "content"],
+			...model.chat
+				.filter((m) => !m.pruned)
+				.map((m) => [m.role, m.action ?? "", m.content]),
+		],
+		options,
+	);
+}
+
+// ── Plain text ──
+
+/**
+ * Greedy word wrap; continuation lines are indented by `indent` spaces.
+ * Words longer than a line (URLs, spelled-out code) are split across lines.
+ */
+export function wrapText(text: string, width: number, indent = 0): string {
+	const pad = " ".repeat(indent);
+	const lines: string[] = [];
+	let line = "";
+	for (let word of text.split(/\s+/).filter(Boolean)) {
+		while (word) {
+			const limit = Math.max(1, lines.length === 0 ? width : width - indent);
+			const room = line ? limit - line.length - 1 : limit;
+			if (word.length <= room) {
+				line = line ? `${line} ${word}` : word;
+				break;
+			}
+			if (word.length <= limit || room <= 0) {
+				lines.push(line);
+				line = "";
+				continue;
+			}
+			// Too long for any line: fill this one and carry the rest over
+			const head = word.slice(0, room);
+			lines.push(line ? `${line} ${head}` : head);
+			line = "";
+			word = word.slice(room);
+		}
+	}
+	if (line) lines.push(line);
+	return lines.map((l, i) => (i === 0 ? l : pad + l)).join("\n");
+}
+
+function renderTxt(model: RenderModel): string {
+	const out = [model.title, model.startedAt, ""];
+	if (model.markers.length > 0) {
+		out.push("Markers:");
+		for (const m of model.markers) {
+			const line = `[${m.time}] ${m.label}${m.quote ? ` — ${m.quote}` : ""}`;
+			out.push(`  ${wrapText(line, TXT_WIDTH - 2, 4)}`);
+		}
+		out.push("");
+	}
+	for (const e of model.entries) {
+		const prefix = `[${e.time}] ${e.speaker}: `;
+		out.push(wrapText(`${prefix}${e.text}`, TXT_WIDTH, prefix.length));
+	}
+	return `${out.join("\n")}\n`;
+}
+
+// ── HTML ──
+
+export function escapeHtml(text: string): string {
+	return text
+		.replace(/&/g, "&amp;")
+		.replace(/</g, "&lt;")
+		.replace(/>/g, "&gt;")
+		.replace(/"/g, "&quot;")
+		.replace(/'/g, "&#39;");
+}
+
+const HTML_STYLE = `body{font-family:system-ui,sans-serif;max-width:860px;margin:2rem auto;padding:0 1rem;color:#111827;line-height:1.5}
+h1{font-size:1.4rem;margin-bottom:0}.meta{color:#6b7280;font-size:.85rem}
+dl{display:grid;grid-template-columns:max-content 1fr;gap:.25rem 1rem}
+dt{color:#6b7280;font-family:monospace;font-size:.8rem}dd{margin:0}dd b{color:#374151}
+nav li a{color:#b45309}details{margin-top:2rem}pre{white-space:pre-wrap}`;
+
+function renderHtml(model: RenderModel): string {
+	const toc = model.markers
+		.map((m) => {
+			const quote = m.quote ? ` — ${escapeHtml(m.quote)}` : "";
+			const label = `${escapeHtml(m.time)} ${escapeHtml(m.label)}${quote}`;
+			return m.anchor
+				? `<li><a href="#${m.anchor}">${label}</a></li>`
+				: `<li>${label}</li>`;
+		})
+		.join("\n");
+	const transcript = model.entries
+		.map(
+			(e) =>
+				`<dt id="${e.anchor}">${escapeHtml(e.time)}</dt><dd><b>${escapeHtml(e.speaker)}</b> ${escapeHtml(e.text)}</dd>`,
+		)
+		.join("\n");
+	const chat = model.chat
+		.map((m) => `<h3>${m.role}</h3>\n<pre>${escapeHtml(m.content)}</pre>`)
+		.join("\n");
+
+	return `<!doctype html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>${escapeHtml(model.title)}</title>
+<style>${HTML_STYLE}</style>
+</head>
+<body>
+<h1>${escapeHtml(model.title)}</h1>
+<p class="meta">${escapeHtml(model.startedAt)}</p>
+${toc ? `<nav><h2>Markers</h2><ul>\n${toc}\n</ul></nav>` : ""}
+<h2>Transcript</h2>
+<dl>
+${transcript}
+</dl>
+${chat ? `<details><summary>AI chat (${model.chat.length} messages)</summary>\n${chat}\n</details>` : ""}
+</body>
+</html>
+`;
+}
+
+export function exportSession(
+	session: Session,
+	format: ExportFormat,
+	options: ExportOptions = {},
+): { filename: string; content: string; mimeType: string } {
+	const base =
+		session.title.replace(/[\\/:*?"<>|]+/g, "_").trim() || "session";
+	const model = buildRenderModel(session);
+	switch (format) {
+		case "json":
+			return {
+				filename: `${base}.json`,
+				content: renderJson(session),
+				mimeType: "application/json",
+			};
+		case "csv":
+			return {
+				filename: `${base}.csv`,
+				content: renderTranscriptCsv(model, options),
+				mimeType: "text/csv",
+			};
+		case "chat-csv":
+			return {
+				filename: `${base}-chat.csv`,
+				content: renderChatCsv(model, options),
+				mimeType: "text/csv",
+			};
+		case "txt":
+			return {
+				filename: `${base}.txt`,
+				content: renderTxt(model),
+				mimeType: "text/plain",
+			};
+		case "html":
+			return {
+				filename: `${base}.html`,
+				content: renderHtml(model),
+				mimeType: "text/html",
+			};
+	}
+}
+
+/** Save a string as a file via a temporary object URL (extension pages only). */
+export function downloadFile(
+	filename: string,
+	content: string,
+	mimeType: string,
+): void {
+	const url = URL.createObjectURL(
+		new Blob([content], { type: `${mimeType};charset=utf-8` }),
+	);
+	const a = document.createElement("a");
+	a.href = url;
+	a.download = filename;
+	a.click();
+	setTimeout(() => URL.revokeObjectURL(url), 0);
+}
@@ -0,0 +1,135 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import {
+	type ChatCapSettings,
+	getSession,
+	pruneChatHistory,
+	saveSession,
+	splitSession,
+	trimSession,
+} from "./session-storage";
+import type { ChatMessage, Session } from "./types";
+
+const cap = (maxChatMessages: number): ChatCapSettings => ({
+	maxChatMessages,
+	preserveAllChat: false,
+});
+
+// `pairs` question/answer pairs; answers use `action` (default "chat")
+function chat(pairs: number, action: ChatMessage["action"] = "chat") {
+	return Array.from({ length: pairs }, (_, i): ChatMessage[] => [
+		{ role: "user", content: `q${i}` },
+		{ role: "assistant", content: `a${i}`, action },
+	]).flat();
+}
+
+describe("pruneChatHistory", () => {
+	it("leaves a history at the cap untouched", () => {
+		const history = chat(10);
+		expect(pruneChatHistory(history, cap(20))).toBe(history);
+	});
+
+	it("drops the oldest pair once over the cap", () => {
+		const history: ChatMessage[] = [
+			...chat(10),
+			{ role: "user", content: "q10" },
+		];
+		const pruned = pruneChatHistory(history, cap(20));
+		expect(pruned).toHaveLength(20);
+		expect(pruned[0]).toMatchObject({ role: "assistant", pruned: 2 });
+		expect(pruned[1].content).toBe("q1");
+		expect(pruned.at(-1)?.content).toBe("q10");
+	});
+
+	it("keeps everything when preserveAllChat is set", () => {
+		const history = chat(50);
+		expect(
+			pruneChatHistory(history, { maxChatMessages: 20, preserveAllChat: true }),
+		).toBe(history);
+	});
+
+	it("never drops recaps or action items", () => {
+		const history = [...chat(5, "recap"), ...chat(20)];
+		const pruned = pruneChatHistory(history, cap(20));
+		expect(pruned.filter((m) => m.action === "recap")).toHaveLength(5);
+	});
+
+	it("updates a single note when pruned again", () => {
+		const once = pruneChatHistory(chat(15), cap(20));
+		expect(once[0].pruned).toBe(12);
+		const twice = pruneChatHistory([...once, ...chat(5)], cap(20));
+		expect(twice.filter((m) => m.pruned)).toHaveLength(1);
+		expect(twice[0].pruned).toBe(22);
+		expect(twice.length).toBeLessThanOrEqual(20);
+	});
+
+	it("does not prune again while the note fits", () => {
+		const once = pruneChatHistory(chat(15), cap(20));
+		expect(pruneChatHistory(once, cap(20))).toBe(once);
+	});
+});
+
+describe("splitSession and trimSession", () => {
+	beforeEach(() => {
+		const store = new Map<string, unknown>();
+		vi.stubGlobal("chrome", {
+			storage: {
+				local: {
+					get: async (key: string) =>
+						store.has(key) ? { [key]: store.get(key) } : {},
+					set: async (items: Record<string, unknown>) => {
+						for (const [k, v] of Object.entries(items)) store.set(k, v);
+					},
+					remove: async (key: string) => {
+						store.delete(key);
+					},
+				},
+			},
+		});
+	});
+
+	afterEach(() => {
+		vi.unstubAllGlobals();
+	});
+
+	const session = (): Session => ({
+		id: "s1",
+		title: "Sync",
+		createdAt: "2026-10-01T01:00:00.000Z",
+		utterances: ["10:00:00", "10:01:00", "10:02:00"].map((time) => ({
+			speaker: "Aoi",
+			text: time,
+			time,
+		})),
+		chatHistory: [],
+		markers: [
+			{ time: "9:59:00", label: "before captions", utteranceIndex: -1 },
+			{ time: "10:01:30", label: "middle", utteranceIndex: 1 },
+		],
+	});
+
+	it("keeps pre-caption markers when trimming from 0", async () => {
+		await saveSession(session());
+		expect(await trimSession("s1", 0, 2)).toBe(true);
+		expect((await getSession("s1"))?.markers?.map((m) => m.label)).toEqual([
+			"before captions",
+			"middle",
+		]);
+	});
+
+	it("drops them once the start is trimmed", async () => {
+		await saveSession(session());
+		await trimSession("s1", 1, 3);
+		expect((await getSession("s1"))?.markers).toEqual([
+			{ time: "10:01:30", label: "middle", utteranceIndex: 0 },
+		]);
+	});
+
+	it("gives the same head markers as trimming", async () => {
+		await saveSession(session());
+		await splitSession("s1", 2);
+		const split = (await getSession("s1"))?.markers;
+		await saveSession(session());
+		await trimSession("s1", 0, 2);
+		expect((await getSession("s1"))?.markers).toEqual(split);
+	});
+});
@@ -1,4 +1,11 @@
-import type { Session, SessionSummary } from "./types";
+import type { ContextSettings } from "./context-settings";
+import { secondsOfDay } from "./time";
+import type {
+	ChatMessage,
+	Marker,
+	Session,
+	SessionSummary,
+} from "./types";
 
 const INDEX_KEY = "sessionIndex";
 const sessionKey = (id: string) => `session:${id}`;
@@ -14,27 +21,113 @@ export async function getSession(id: string): Promise<Session | null> {
 	return (session as Session | undefined) ?? null;
 }
 
-export async function saveSession(session: Session): Promise<void> {
-	const key = sessionKey(session.id);
-	await chrome.storage.local.set({ [key]: session });
-
-	const index = await getSessionIndex();
-	const summary: SessionSummary = {
+export function toSessionSummary(session: Session): SessionSummary {
+	return {
 		id: session.id,
 		title: session.title,
 		createdAt: session.createdAt,
 		utteranceCount: session.utterances.length,
 		chatMessageCount: session.chatHistory.length,
 	};
+}
+
+/**
+ * Save a session and update the index.
+ * Reports whether its index entry was created, changed, or left as it was.
+ */
+export async function saveSession(
+	session: Session,
+): Promise<"created" | "changed" | "unchanged"> {
+	const key = sessionKey(session.id);
+	await chrome.storage.local.set({ [key]: session });
+
+	const index = await getSessionIndex();
+	const summary = toSessionSummary(session);
 
 	const existing = index.findIndex((s) => s.id === session.id);
 	if (existing >= 0) {
+		if (JSON.stringify(index[existing]) === JSON.stringify(summary)) {
+			return "unchanged";
+		}
 		index[existing] = summary;
 	} else {
 		index.unshift(summary);
 	}
 
 	await chrome.storage.local.set({ [INDEX_KEY]: index });
+	return existing < 0 ? "created" : "changed";
+}
+
+// Recaps and action items are the record of the meeting; never prune them
+const PROTECTED_ACTIONS: ChatMessage["action"][] = ["recap", "action"];
+
+export type ChatCapSettings = Pick<
+	ContextSettings,
+	"maxChatMessages" | "preserveAllChat"
+>;
+
+/**
+ * Drop the oldest chat replies (with the question before each) until the
+ * history fits in `maxChatMessages`, leaving a single note at the top saying
+ * how many messages were removed. Returns `history` unchanged if nothing is
+ * dropped or the user keeps all chat.
+ */
+export function pruneChatHistory(
+	history: ChatMessage[],
+	{ maxChatMessages: max, preserveAllChat }: ChatCapSettings,
+): ChatMessage[] {
+	if (preserveAllChat) return history;
+	const previous = history.find((m) => m.pruned)?.pruned ?? 0;
+	const rest = history.filter((m) => !m.pruned);
+	if (rest.length + (previous ? 1 : 0) <= max) return history;
+
+	// +1 leaves room for the note
+	let excess = rest.length + 1 - max;
+	const drop = new Set<number>();
+	for (let i = 0; i < rest.length && excess > 0; i++) {
+		const m = rest[i];
+		if (m.role !== "assistant" || PROTECTED_ACTIONS.includes(m.action)) {
+			continue;
+		}
+		drop.add(i);
+		excess--;
+		if (i > 0 && rest[i - 1].role === "user" && !drop.has(i - 1)) {
+			drop.add(i - 1);
+			excess--;
+		}
+	}
+	if (drop.size === 0) return history;
+
+	const count = previous + drop.size;
+	return [
+		{
+			role: "assistant",
+			content: `${count} earlier messages were removed to keep this session small.`,
+			pruned: count,
+		},
+		...rest.filter((_, i) => !drop.has(i)),
+	];
+}
+
+/**
+ * Apply the chat cap to every stored session except `skipId`.
+ * Returns the ids of the sessions that were pruned.
+ */
+export async function pruneStoredChatHistories(
+	settings: ChatCapSettings,
+	skipId?: string | null,
+): Promise<string[]> {
+	const pruned: string[] = [];
+	for (const { id } of await getSessionIndex()) {
+		if (id === skipId) continue;
+		const session = await getSession(id);
+		if (!session) continue;
+		const chatHistory = pruneChatHistory(session.chatHistory, settings);
+		if (chatHistory === session.chatHistory) continue;
+		await saveSession({ ...session, chatHistory });
+		pruned.push(id);
+	}
+	return pruned;
 }
 
 export async function updateSessionTitle(
@@ -55,6 +148,35 @@ export async function updateSessionTitle(
 	}
 }
 
+export async function updateSessionContext(
+	id: string,
+	note: string,
+): Promise<void> {
+	const session = await getSession(id);
+	if (!session) return;
+
+	session.contextNote = note.trim() || undefined;
+	await chrome.storage.local.set({ [sessionKey(id)]: session });
+}
+
+/**
+ * Rename speakers across a session's utterances.
+ * Labels missing from `mapping` (or mapped to an empty name) are kept.
+ */
+export async function remapSessionSpeakers(
+	id: string,
+	mapping: Record<string, string>,
+): Promise<void> {
+	const session = await getSession(id);
+	if (!session) return;
+
+	session.utterances = session.utterances.map((u) => {
+		const name = mapping[u.speaker]?.trim();
+		return name ? { ...u, speaker: name } : u;
+	});
+	await chrome.storage.local.set({ [sessionKey(id)]: session });
+}
+
 export async function deleteSession(id: string): Promise<void> {
 	await chrome.storage.local.remove(sessionKey(id));
 
@@ -62,3 +184,89 @@ export async function deleteSession(id: string): Promise<void> {
 	const filtered = index.filter((s) => s.id !== id);
 	await chrome.storage.local.set({ [INDEX_KEY]: filtered });
 }
+
+// Utterances only carry a wall-clock "H:MM:SS" string, so the start of a
+// split-off session is derived from the parent's date plus that time.
+function sessionTimeAt(createdAt: string, time: string): string {
+	const seconds = secondsOfDay(time);
+	if (seconds === null) return createdAt;
+	const base = new Date(createdAt);
+	const date = new Date(base);
+	date.setHours(0, 0, seconds, 0);
+	// Meeting crossed midnight
+	if (base.getTime() - date.getTime() > 12 * 60 * 60 * 1000) {
+		date.setDate(date.getDate() + 1);
+	}
+	return date.toISOString();
+}
+
+// Markers dropped before the first caption (index -1) stay with a slice that
+// starts at the beginning
+function sliceMarkers(
+	markers: Marker[] | undefined,
+	start: number,
+	end: number,
+): Marker[] {
+	const from = start === 0 ? Number.NEGATIVE_INFINITY : start;
+	return (markers ?? [])
+		.filter((m) => m.utteranceIndex >= from && m.utteranceIndex < end)
+		.map((m) => ({ ...m, utteranceIndex: m.utteranceIndex - start }));
+}
+
+/**
+ * Split a session before the utterance at `at`.
+ * The original keeps the head (and its chat history); the tail becomes a new
+ * session. Returns the new session's id, or null if the index is out of range.
+ */
+export async function splitSession(
+	id: string,
+	at: number,
+): Promise<string | null> {
+	const session = await getSession(id);
+	if (!session || at <= 0 || at >= session.utterances.length) return null;
+
+	const tail: Session = {
+		id: crypto.randomUUID(),
+		title: `${session.title} (2)`,
+		meetUrl: session.meetUrl,
+		createdAt: sessionTimeAt(session.createdAt, session.utterances[at].time),
+		utterances: session.utterances.slice(at),
+		chatHistory: [],
+		markers: sliceMarkers(session.markers, at, session.utterances.length),
+	};
+	const head: Session = {
+		...session,
+		utterances: session.utterances.slice(0, at),
+		markers: sliceMarkers(session.markers, 0, at),
+	};
+
+	await saveSession(head);
+	await saveSession(tail);
+	return tail.id;
+}
+
+/**
+ * Keep only utterances in [start, end). Markers outside the range are dropped,
+ * except those from before the first caption when the range starts at 0.
+ * Returns false if the range is empty or out of bounds.
+ */
+export async function trimSession(
+	id: string,
+	start: number,
+	end: number,
+): Promise<boolean> {
+	const session = await getSession(id);
+	if (!session || start < 0 || end > session.utterances.length || start >= end)
+		return false;
+
+	await saveSession({
+		...session,
+		createdAt:
+			start > 0
+				? sessionTimeAt(session.createdAt, session.utterances[start].time)
+				: session.createdAt,
+		utterances: session.utterances.slice(start, end),
+		markers: sliceMarkers(session.markers, start, end),
+	});
+	return true;
+}
@@ -0,0 +1,23 @@
+/** Seconds since midnight for a caption "H:MM:SS" time, or null if malformed. */
+export function secondsOfDay(time: string): number | null {
+	const match = time.match(/^(\d{1,2}):(\d{2}):(\d{2})$/);
+	if (!match) return null;
+	return Number(match[1]) * 3600 + Number(match[2]) * 60 + Number(match[3]);
+}
+
+/**
+ * Parse a user-entered "H:MM" or "H:MM:SS" time into seconds since midnight.
+ * With `endOfMinute`, "H:MM" covers the whole minute (e.g. 10:20 → 10:20:59).
+ */
+export function parseClockTime(
+	input: string,
+	endOfMinute = false,
+): number | null {
+	const match = input.trim().match(/^(\d{1,2}):(\d{2})(?::(\d{2}))?$/);
+	if (!match) return null;
+	const [h, m] = [Number(match[1]), Number(match[2])];
+	if (h > 23 || m > 59) return null;
+	const s = match[3] !== undefined ? Number(match[3]) : endOfMinute ? 59 : 0;
+	if (s > 59) return null;
+	return h * 3600 + m * 60 + s;
+}
@@ -4,18 +4,61 @@ export interface Utterance {
 	time: string;
 }
 
+export interface Marker {
+	time: string;
+	label: string;
+	/** Index of the latest utterance when the marker was added (-1 if none). */
+	utteranceIndex: number;
+}
+
+/** User feedback on an AI reply. Using a reply and rating it are independent. */
+export interface AiFeedback {
+	used?: boolean;
+	rating?: "helpful" | "not-helpful";
+}
+
+export type AiFeedbackOption = "used" | "helpful" | "not-helpful";
+
+/** Clock-time window ("H:MM" or "H:MM:SS") an AI request is limited to. */
+export interface TimeRange {
+	from: string;
+	to: string;
+}
+
 export interface ChatMessage {
 	role: "user" | "assistant";
 	content: string;
+	/** Response language override used for this reply, if any. */
+	language?: string;
+	/** AI action (or "chat") that produced this reply. Unset on errors. */
+	action?: AiAction | "chat";
+	feedback?: AiFeedback;
+	/** Time range the reply was limited to, if any. */
+	range?: TimeRange;
+	/** Set on the note that stands in for pruned messages: how many. */
+	pruned?: number;
 }
 
+/** Feedback counts per action, e.g. `{ assist: { used: 3, ... } }`. */
+export type AiFeedbackStats = Record<string, Record<AiFeedbackOption, number>>;
+
 export interface AiResponse {
 	action: string;
 	content: string;
 	timestamp: string;
+	language?: string;
+	range?: TimeRange;
+	/** Set when `content` is an error message rather than a reply. */
+	error?: boolean;
 }
 
-export type AiAction = "recap" | "assist" | "question" | "action";
+export type AiAction =
+	| "recap"
+	| "assist"
+	| "question"
+	| "action"
+	| "objection"
+	| "explain";
 
 export type View = "transcript" | "chat" | "settings" | "sessions";
 
@@ -26,6 +69,11 @@ export interface Session {
 	createdAt: string;
 	utterances: Utterance[];
 	chatHistory: ChatMessage[];
+	markers?: Marker[];
+	/** Last response language override chosen in this session. */
+	responseLanguage?: string;
+	/** User-written note about the meeting, sent to the AI with the transcript. */
+	contextNote?: string;
 }
 
 export interface SessionSummary {
@@ -35,3 +83,19 @@ export interface SessionSummary {
 	utteranceCount: number;
 	chatMessageCount: number;
 }
+
+/** Minimal diff broadcast whenever stored sessions change. */
+export interface SessionsChange {
+	added: SessionSummary[];
+	removed: string[];
+	updated: string[];
+}
+
+export interface AppInfo {
+	version: string;
+	os: string;
+	arch: string;
+	browserVersion: string;
+	sessionCount: number;
+	utteranceCount: number;
+}
@@ -0,0 +1,77 @@
+import { beforeEach, describe, expect, it } from "vitest";
+import {
+	activeTimeRange,
+	timeRangeError,
+	useMeetingStore,
+} from "./meetingStore";
+
+const add = (speaker: string, text: string, time: string) =>
+	useMeetingStore.getState().addUtterance({ speaker, text, time });
+
+const texts = () => useMeetingStore.getState().utterances.map((u) => u.text);
+
+describe("addUtterance duplicate handling", () => {
+	beforeEach(() => {
+		useMeetingStore.getState().reset();
+	});
+
+	it("drops a re-rendered line shortly after another speaker", () => {
+		add("Aoi", "Let's ship it on Friday", "10:00:00");
+		add("Ben", "Sounds good to me", "10:00:03");
+		add("Aoi", "Let's ship it on Friday", "10:00:06");
+		expect(texts()).toEqual(["Let's ship it on Friday", "Sounds good to me"]);
+	});
+
+	it("measures the window from the speaker's last update", () => {
+		const line = "So the plan is to migrate the billing service first";
+		add("Aoi", "So the plan is", "10:00:00");
+		add("Aoi", line, "10:00:40");
+		add("Ben", "Agreed", "10:00:42");
+		add("Aoi", line, "10:00:45");
+		expect(texts()).toEqual([line, "Agreed"]);
+	});
+
+	it("keeps a repeat that arrives after the window", () => {
+		add("Aoi", "Let's ship it on Friday", "10:00:00");
+		add("Ben", "Why Friday though?", "10:00:05");
+		add("Aoi", "Let's ship it on Friday", "10:00:20");
+		expect(texts()).toHaveLength(3);
+	});
+
+	it("keeps short replies that are genuinely repeated", () => {
+		add("Aoi", "Yes", "10:00:00");
+		add("Ben", "Ready?", "10:00:02");
+		add("Aoi", "Yes", "10:00:04");
+		expect(texts()).toEqual(["Yes", "Ready?", "Yes"]);
+	});
+
+	it("keeps the same line from a different speaker", () => {
+		add("Aoi", "Let's ship it on Friday", "10:00:00");
+		add("Ben", "Let's ship it on Friday", "10:00:02");
+		expect(texts()).toHaveLength(2);
+	});
+
+	it("handles the window across midnight", () => {
+		add("Aoi", "Happy new year everyone", "23:59:58");
+		add("Ben", "Cheers", "0:00:01");
+		add("Aoi", "Happy new year everyone", "0:00:03");
+		expect(texts()).toEqual(["Happy new year everyone", "Cheers"]);
+	});
+});
+
+describe("time range", () => {
+	it("is active only with both ends filled in", () => {
+		expect(activeTimeRange({ from: " 10:05 ", to: "10:20" })).toEqual({
+			from: "10:05",
+			to: "10:20",
+		});
+		expect(activeTimeRange({ from: "10:05", to: " " })).toBeUndefined();
+	});
+
+	it("flags a half-filled range", () => {
+		expect(timeRangeError({ from: "10:05", to: "" })).not.toBeNull();
+		expect(timeRangeError({ from: "", to: "10:20" })).not.toBeNull();
+		expect(timeRangeError({ from: "", to: "" })).toBeNull();
+		expect(timeRangeError({ from: "10:05", to: "10:20" })).toBeNull();
+	});
+});
@@ -1,8 +1,12 @@
 import { create } from "zustand";
+import { secondsOfDay } from "../lib/time";
 import type {
+	AiFeedback,
 	AiResponse,
 	ChatMessage,
+	Marker,
 	Session,
+	TimeRange,
 	Utterance,
 	View,
 } from "../lib/types";
@@ -10,12 +14,30 @@ import type {
 interface MeetingState {
 	utterances: Utterance[];
 	addUtterance: (u: Utterance) => void;
+	// When each speaker's latest caption arrived (new line or update)
+	lastCaptionAt: Record<string, string>;
 
 	aiResponses: AiResponse[];
 	addAiResponse: (r: AiResponse) => void;
 
 	chatHistory: ChatMessage[];
 	addChatMessage: (m: ChatMessage) => void;
+	setChatFeedback: (index: number, feedback: AiFeedback | undefined) => void;
+
+	markers: Marker[];
+	addMarker: (label?: string) => void;
+
+	// Response language override (null = same as transcript)
+	responseLanguage: string | null;
+	setResponseLanguage: (language: string | null) => void;
+
+	// Per-meeting note sent to the AI alongside the transcript
+	contextNote: string;
+	setContextNote: (note: string) => void;
+
+	// Optional clock-time window AI requests are limited to
+	timeRange: TimeRange;
+	setTimeRange: (range: Partial<TimeRange>) => void;
 
 	isAiLoading: boolean;
 	setAiLoading: (loading: boolean) => void;
@@ -38,10 +60,37 @@ interface MeetingState {
 	reset: () => void;
 }
 
+// Meet sometimes re-renders an earlier caption block after another speaker
+// has talked, which would re-append the same line. Skip such repeats when
+// they arrive within this window of that speaker's last caption update.
+const DUPLICATE_WINDOW_SECONDS = 10;
+// Short replies ("Yes", "OK") are often genuinely repeated; keep them
+const DUPLICATE_MIN_LENGTH = 10;
+
+function isRecentDuplicate(
+	prev: Utterance[],
+	u: Utterance,
+	lastSeenAt: string | undefined,
+): boolean {
+	const text = u.text.trim();
+	if (text.length < DUPLICATE_MIN_LENGTH) return false;
+	const p = prev.findLast((e) => e.speaker === u.speaker);
+	if (!p || p.text.trim() !== text) return false;
+	const at = secondsOfDay(u.time);
+	const seen = secondsOfDay(lastSeenAt ?? p.time);
+	if (at === null || seen === null) return false;
+	return (at - seen + 86400) % 86400 <= DUPLICATE_WINDOW_SECONDS;
+}
+
 const initialState = {
 	utterances: [] as Utterance[],
+	lastCaptionAt: {} as Record<string, string>,
 	aiResponses: [] as AiResponse[],
 	chatHistory: [] as ChatMessage[],
+	markers: [] as Marker[],
+	responseLanguage: null as string | null,
+	contextNote: "",
+	timeRange: { from: "", to: "" } as TimeRange,
 	isAiLoading: false,
 	currentView: "transcript" as View,
 	isMeetingContext: false,
@@ -50,6 +99,21 @@ const initialState = {
 	sessionCreatedAt: null as string | null,
 };
 
+/** Error to show while only one end of the time range is filled in. */
+export function timeRangeError(range: TimeRange): string | null {
+	const from = range.from.trim();
+	const to = range.to.trim();
+	if (!from === !to) return null;
+	return "Fill in both times, or clear them to use the whole meeting.";
+}
+
+/** The store's time range, or undefined unless both ends are filled in. */
+export function activeTimeRange(range: TimeRange): TimeRange | undefined {
+	const from = range.from.trim();
+	const to = range.to.trim();
+	return from && to ? { from, to } : undefined;
+}
+
 export const useMeetingStore = create<MeetingState>((set, get) => ({
 	...initialState,
 
@@ -57,14 +121,19 @@ export const useMeetingStore = create<MeetingState>((set, get) => ({
 		set((state) => {
 			const prev = state.utterances;
 			const last = prev[prev.length - 1];
+			const lastCaptionAt = { ...state.lastCaptionAt, [u.speaker]: u.time };
 			// Same speaker still talking → update text of last entry
 			if (last && last.speaker === u.speaker) {
 				const updated = [...prev];
 				updated[updated.length - 1] = { ...last, text: u.text };
-				return { utterances: updated };
+				return { utterances: updated, lastCaptionAt };
+			}
+			// Same line from this speaker just re-rendered → ignore
+			if (isRecentDuplicate(prev, u, state.lastCaptionAt[u.speaker])) {
+				return { lastCaptionAt };
 			}
 			// New speaker → append new entry
-			return { utterances: [...prev, u] };
+			return { utterances: [...prev, u], lastCaptionAt };
 		}),
 
 	addAiResponse: (r) =>
@@ -73,6 +142,32 @@ export const useMeetingStore = create<MeetingState>((set, get) => ({
 	addChatMessage: (m) =>
 		set((state) => ({ chatHistory: [...state.chatHistory, m] })),
 
+	setChatFeedback: (index, feedback) =>
+		set((state) => ({
+			chatHistory: state.chatHistory.map((m, i) =>
+				i === index ? { ...m, feedback } : m,
+			),
+		})),
+
+	addMarker: (label = "") =>
+		set((state) => {
+			// Markers belong to the active session only
+			if (!state.sessionId) return {};
+			const marker: Marker = {
+				time: new Date().toLocaleTimeString("ja-JP", { hour12: false }),
+				label: label.trim(),
+				utteranceIndex: state.utterances.length - 1,
+			};
+			return { markers: [...state.markers, marker] };
+		}),
+
+	setResponseLanguage: (language) => set({ responseLanguage: language }),
+
+	setContextNote: (note) => set({ contextNote: note }),
+
+	setTimeRange: (range) =>
+		set((state) => ({ timeRange: { ...state.timeRange, ...range } })),
+
 	setAiLoading: (loading) => set({ isAiLoading: loading }),
 
 	setView: (view) => set({ currentView: view }),
@@ -15,6 +15,12 @@ export default defineConfig({
 			"http://localhost:*/*",
 			"http://127.0.0.1:*/*",
 		],
+		commands: {
+			"add-marker": {
+				suggested_key: { default: "Alt+Shift+M" },
+				description: "Add a marker to the current meeting",
+			},
+		},
 	},
 	vite: () => ({
 		plugins: [tailwindcss()],